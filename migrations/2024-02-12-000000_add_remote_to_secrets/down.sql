@@ -0,0 +1,3 @@
+DROP INDEX secrets_remote_path;
+
+ALTER TABLE secrets DROP COLUMN remote;
@@ -0,0 +1,6 @@
+ALTER TABLE secrets ADD COLUMN remote TEXT;
+
+-- Projects keyed by their remote URL before the column existed
+UPDATE secrets SET remote = project WHERE project LIKE '%/%';
+
+CREATE UNIQUE INDEX secrets_remote_path ON secrets (remote, path) WHERE remote IS NOT NULL;
@@ -0,0 +1,3 @@
+ALTER TABLE secrets DROP COLUMN synced_at;
+
+ALTER TABLE secrets DROP COLUMN updated_at;
@@ -0,0 +1,3 @@
+ALTER TABLE secrets ADD COLUMN updated_at BIGINT NOT NULL DEFAULT 0;
+
+ALTER TABLE secrets ADD COLUMN synced_at BIGINT NOT NULL DEFAULT 0;
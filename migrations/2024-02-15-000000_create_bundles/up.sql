@@ -0,0 +1,7 @@
+CREATE TABLE bundles (
+    name TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    -- A config shorthand, or another bundle's name prefixed with @
+    member TEXT NOT NULL,
+    PRIMARY KEY (name, position)
+);
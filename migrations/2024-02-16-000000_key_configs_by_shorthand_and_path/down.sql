@@ -0,0 +1,13 @@
+CREATE TABLE configs_old (
+    filename TEXT NOT NULL PRIMARY KEY,
+    shorthand TEXT NOT NULL,
+    content TEXT NOT NULL,
+    variables TEXT
+);
+
+INSERT OR IGNORE INTO configs_old (filename, shorthand, content, variables)
+SELECT path, shorthand, content, variables FROM configs;
+
+DROP TABLE configs;
+
+ALTER TABLE configs_old RENAME TO configs;
@@ -0,0 +1,16 @@
+-- SQLite cannot change a primary key in place, so the table is rebuilt
+CREATE TABLE configs_new (
+    shorthand TEXT NOT NULL,
+    -- Target path relative to the project folder, may include subdirectories
+    path TEXT NOT NULL,
+    content TEXT NOT NULL,
+    variables TEXT,
+    PRIMARY KEY (shorthand, path)
+);
+
+INSERT INTO configs_new (shorthand, path, content, variables)
+SELECT shorthand, filename, content, variables FROM configs;
+
+DROP TABLE configs;
+
+ALTER TABLE configs_new RENAME TO configs;
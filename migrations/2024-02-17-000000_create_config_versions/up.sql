@@ -0,0 +1,14 @@
+-- Every revision is a full snapshot of the files of a config shorthand
+CREATE TABLE config_versions (
+    shorthand TEXT NOT NULL,
+    revision INTEGER NOT NULL,
+    path TEXT NOT NULL,
+    content TEXT NOT NULL,
+    message TEXT NOT NULL,
+    created_at BIGINT NOT NULL,
+    PRIMARY KEY (shorthand, revision, path)
+);
+
+INSERT INTO config_versions (shorthand, revision, path, content, message, created_at)
+SELECT shorthand, 1, path, content, 'Initial version', CAST(strftime('%s', 'now') AS INTEGER)
+FROM configs;
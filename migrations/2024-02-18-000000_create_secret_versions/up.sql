@@ -0,0 +1,19 @@
+-- Encrypted contents of every secret, the newest revision being the current content
+CREATE TABLE secret_versions (
+    project TEXT NOT NULL,
+    path TEXT NOT NULL,
+    revision INTEGER NOT NULL,
+    content TEXT NOT NULL,
+    created_at BIGINT NOT NULL,
+    PRIMARY KEY (project, path, revision)
+);
+
+-- Secrets stored before timestamps were recorded have an updated_at of 0
+INSERT INTO secret_versions (project, path, revision, content, created_at)
+SELECT
+    project,
+    path,
+    1,
+    content,
+    CASE updated_at WHEN 0 THEN CAST(strftime('%s', 'now') AS INTEGER) ELSE updated_at END
+FROM secrets;
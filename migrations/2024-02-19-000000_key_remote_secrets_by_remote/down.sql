@@ -0,0 +1,2 @@
+-- The folder names of secrets stored under their remote are not kept, so they stay keyed by remote
+SELECT 1;
@@ -0,0 +1,14 @@
+-- Secrets with a remote are stored under the remote instead of the folder name, so projects
+-- with the same folder name but different remotes cannot overwrite each other
+UPDATE secret_versions SET project = (
+    SELECT remote FROM secrets
+    WHERE secrets.project = secret_versions.project AND secrets.path = secret_versions.path
+)
+WHERE EXISTS (
+    SELECT 1 FROM secrets
+    WHERE secrets.project = secret_versions.project
+        AND secrets.path = secret_versions.path
+        AND secrets.remote IS NOT NULL
+);
+
+UPDATE secrets SET project = remote WHERE remote IS NOT NULL;
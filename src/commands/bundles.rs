@@ -0,0 +1,229 @@
+use {
+    crate::{
+        error::{Error, Result},
+        models::Bundle,
+        output::{self, Field, Record},
+        schema::{bundles, configs},
+        success,
+    },
+    diesel::prelude::*,
+    std::collections::HashMap,
+};
+
+type Bundles = HashMap<String, Vec<String>>;
+
+fn load() -> Result<Bundles> {
+    let rows = bundles::dsl::bundles
+        .order((bundles::name, bundles::position))
+        .load::<Bundle>(&mut crate::connect_db()?)
+        .map_err(|err| Error::Database("Unable to fetch bundles".into(), err))?;
+
+    let mut bundles = Bundles::new();
+    for row in rows {
+        bundles.entry(row.name).or_default().push(row.member);
+    }
+    Ok(bundles)
+}
+
+fn visit(
+    member: &str,
+    bundles: &Bundles,
+    stack: &mut Vec<String>,
+    shorthands: &mut Vec<String>,
+) -> Result<()> {
+    let name = match member.strip_prefix('@') {
+        Some(name) => name,
+        None => {
+            if !shorthands.iter().any(|shorthand| shorthand == member) {
+                shorthands.push(member.to_string());
+            }
+            return Ok(());
+        }
+    };
+
+    if let Some(start) = stack.iter().position(|visited| visited == name) {
+        let cycle = stack[start..]
+            .iter()
+            .map(String::as_str)
+            .chain([name])
+            .map(|name| format!("@{}", name))
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        return Err(Error::Conflict(format!(
+            "Bundle \"@{}\" includes itself through \"{}\"",
+            name, cycle
+        )));
+    }
+
+    let members = bundles
+        .get(name)
+        .ok_or_else(|| Error::NotFound(format!("Unknown bundle \"@{}\"", name)))?;
+
+    stack.push(name.to_string());
+    for member in members {
+        visit(member, bundles, stack, shorthands)?;
+    }
+    stack.pop();
+
+    Ok(())
+}
+
+/// Expands `@bundle` arguments into their member shorthands, keeping the first of any duplicates
+pub fn expand(arguments: &[String]) -> Result<Vec<String>> {
+    let bundles = match arguments.iter().any(|argument| argument.starts_with('@')) {
+        true => load()?,
+        false => Bundles::new(),
+    };
+
+    let mut shorthands = vec![];
+    for argument in arguments {
+        visit(argument, &bundles, &mut vec![], &mut shorthands)?;
+    }
+    Ok(shorthands)
+}
+
+fn list() -> seahorse::Command {
+    seahorse::Command::new("list")
+        .description("List all bundles and the shorthands they include")
+        .usage("nova configs bundle list")
+        .action(|context| {
+            crate::run(context, |_| {
+                let mut bundles = load()?.into_iter().collect::<Vec<_>>();
+                bundles.sort();
+
+                let records = bundles
+                    .into_iter()
+                    .map(|(name, members)| Record {
+                        shorthand: format!("@{}", name),
+                        members,
+                        ..Default::default()
+                    })
+                    .collect::<Vec<_>>();
+
+                output::print_records(
+                    &[("Bundle", Field::Shorthand), ("Members", Field::Members)],
+                    &records,
+                )?;
+                Ok(())
+            })
+        })
+}
+
+fn add() -> seahorse::Command {
+    seahorse::Command::new("add")
+        .description("Add a bundle of shorthands and other @bundles to clone together")
+        .usage("nova configs bundle add [name] [...members]")
+        .action(|context| {
+            crate::run(context, |context| {
+                let name = context
+                    .args
+                    .first()
+                    .map(|name| name.trim_start_matches('@'))
+                    .filter(|name| !name.is_empty())
+                    .ok_or_else(|| {
+                        Error::Usage("Please provide a bundle name, then its members".into())
+                    })?;
+
+                let members = context.args[1..].to_vec();
+                if members.is_empty() {
+                    return Err(Error::Usage(
+                        "Please provide some shorthands or @bundles to include".into(),
+                    ));
+                }
+
+                let mut bundles = load()?;
+                if bundles.contains_key(name) {
+                    return Err(Error::Conflict("Bundle already exists".into()));
+                }
+
+                for member in members.iter().filter(|member| !member.starts_with('@')) {
+                    let shorthands = configs::dsl::configs
+                        .filter(configs::shorthand.eq(member))
+                        .count()
+                        .get_result::<i64>(&mut crate::connect_db()?)
+                        .map_err(|err| Error::Database("Unable to fetch configs".into(), err))?;
+                    if shorthands == 0 {
+                        return Err(Error::NotFound(format!(
+                            "Unknown config shorthand \"{}\"",
+                            member
+                        )));
+                    }
+                }
+
+                bundles.insert(name.to_string(), members.clone());
+                visit(&format!("@{}", name), &bundles, &mut vec![], &mut vec![])?;
+
+                let rows = members
+                    .into_iter()
+                    .enumerate()
+                    .map(|(position, member)| Bundle {
+                        name: name.to_string(),
+                        position: position as i32,
+                        member,
+                    })
+                    .collect::<Vec<_>>();
+
+                diesel::insert_into(bundles::dsl::bundles)
+                    .values(&rows)
+                    .execute(&mut crate::connect_db()?)
+                    .map_err(|err| {
+                        Error::Database(format!("Unable to store new bundle \"@{}\"", name), err)
+                    })?;
+
+                success!("Added bundle", format!("@{}", name));
+                Ok(())
+            })
+        })
+}
+
+fn remove() -> seahorse::Command {
+    seahorse::Command::new("remove")
+        .description("Remove a bundle, the configs it includes are kept")
+        .usage("nova configs bundle remove [name]")
+        .action(|context| {
+            crate::run(context, |context| {
+                let name = context
+                    .args
+                    .first()
+                    .map(|name| name.trim_start_matches('@'))
+                    .ok_or_else(|| Error::Usage("Please provide a bundle name".into()))?;
+
+                let mut includers = load()?
+                    .into_iter()
+                    .filter(|(_, members)| members.contains(&format!("@{}", name)))
+                    .map(|(includer, _)| format!("@{}", includer))
+                    .collect::<Vec<_>>();
+                if !includers.is_empty() {
+                    includers.sort();
+                    return Err(Error::Conflict(format!(
+                        "Bundle \"@{}\" is included by \"{}\"",
+                        name,
+                        includers.join("\", \"")
+                    )));
+                }
+
+                let deleted = diesel::delete(bundles::dsl::bundles)
+                    .filter(bundles::name.eq(name))
+                    .execute(&mut crate::connect_db()?)
+                    .map_err(|err| {
+                        Error::Database(format!("Unable to delete bundle \"@{}\"", name), err)
+                    })?;
+
+                if deleted == 0 {
+                    return Err(Error::NotFound(format!("Unknown bundle \"@{}\"", name)));
+                }
+
+                success!("Removed bundle", format!("@{}", name));
+                Ok(())
+            })
+        })
+}
+
+pub fn bundle() -> seahorse::Command {
+    seahorse::Command::new("bundle")
+        .description("Manage named groups of configs that are cloned together with @name")
+        .command(list())
+        .command(add())
+        .command(remove())
+        .action(crate::help)
+}
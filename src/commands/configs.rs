@@ -1,269 +1,957 @@
 use {
-    crate::{error, models::Config, schema::configs, success, warn},
+    super::bundles,
+    crate::{
+        diff, editor,
+        error::{Error, Result},
+        history, merge,
+        models::Config,
+        output::{self, Field, Format, Record},
+        overwrite::{Outcome, Overwrite},
+        prompt,
+        schema::configs,
+        settings, success,
+        template::{self, Prompts, Variables},
+        time, warn,
+    },
     diesel::prelude::*,
 };
 
-fn list() -> seahorse::Command {
-    seahorse::Command::new("list")
-        .description("List all project configuration file(s) and their shorthands")
-        .usage("nova configs list")
-        .action(|_| {
-            let configs = configs::dsl::configs
-                .load::<Config>(&mut crate::connect_db())
-                .unwrap();
-
-            let mut table = prettytable::Table::new();
-            table.set_titles(prettytable::row!["Shorthand", "Filename", "Content Length"]);
-
-            for config in configs {
-                table.add_row(prettytable::row![
-                    config.shorthand,
-                    config.filename,
-                    config.content.len()
-                ]);
+/// Fetches every file of a config shorthand
+fn find(shorthand: &str) -> Result<Vec<Config>> {
+    let configs = configs::dsl::configs
+        .filter(configs::shorthand.eq(shorthand))
+        .order(configs::path)
+        .load::<Config>(&mut crate::connect_db()?)
+        .map_err(|err| Error::Database("Unable to fetch configs".into(), err))?;
+
+    if configs.is_empty() {
+        return Err(Error::NotFound(format!(
+            "Unknown config shorthand \"{}\"",
+            shorthand
+        )));
+    }
+
+    Ok(configs)
+}
+
+/// Fetches one file of a config shorthand, the path is only needed if it has several files
+fn find_one(shorthand: &str, path: Option<&String>) -> Result<Config> {
+    let mut configs = find(shorthand)?;
+
+    match path {
+        Some(path) => {
+            let path = target_path(path)?;
+            configs
+                .into_iter()
+                .find(|config| config.path == path)
+                .ok_or_else(|| {
+                    Error::NotFound(format!("Config \"{}\" has no file \"{}\"", shorthand, path))
+                })
+        }
+        None if configs.len() == 1 => Ok(configs.remove(0)),
+        None => Err(Error::Usage(format!(
+            "Config \"{}\" has several files, please provide one of \"{}\"",
+            shorthand,
+            configs
+                .iter()
+                .map(|config| config.path.as_str())
+                .collect::<Vec<_>>()
+                .join("\", \"")
+        ))),
+    }
+}
+
+/// Normalizes a path relative to the cwd into the path a config is cloned to
+fn target_path(path: &str) -> Result<String> {
+    let normalized = path.replace('\\', "/");
+    let mut parts = vec![];
+    for component in std::path::Path::new(&normalized).components() {
+        match component {
+            std::path::Component::Normal(part) => parts.push(part.to_str().unwrap()),
+            std::path::Component::CurDir => {}
+            _ => {
+                return Err(Error::Usage(format!(
+                    "Config path \"{}\" must be inside the current working directory",
+                    path
+                )))
             }
+        }
+    }
 
-            table.printstd();
-        })
+    if parts.is_empty() {
+        return Err(Error::Usage("Please provide a filename".into()));
+    }
+
+    Ok(parts.join("/"))
 }
 
-fn clone() -> seahorse::Command {
-    seahorse::Command::new("clone")
-        .description("Clone project configuration file(s) to the current working directory")
-        .usage("nova configs clone [...shorthands]")
-        .action(|context| {
-            for shorthand in &context.args {
-                let config = match configs::dsl::configs
-                    .filter(configs::shorthand.eq(shorthand))
-                    .first::<Config>(&mut crate::connect_db())
-                {
-                    Ok(config) => config,
-                    Err(_) => {
-                        error!("Unknown config shorthand", shorthand);
-                        continue;
-                    }
-                };
+/// Lists every file under a folder, sorted by path
+fn walk(folder: &std::path::Path) -> Result<Vec<std::path::PathBuf>> {
+    let entries = std::fs::read_dir(folder).map_err(|err| {
+        Error::Io(
+            format!("Unable to read folder \"{}\"", folder.display()),
+            err,
+        )
+    })?;
 
-                if let Err(err) =
-                    std::fs::write(std::path::PathBuf::from(&config.filename), config.content)
-                {
-                    error!("Unable to write to file", config.filename; err);
-                    return;
-                }
+    let mut files = vec![];
+    for entry in entries {
+        let path = entry
+            .map_err(|err| {
+                Error::Io(
+                    format!("Unable to read folder \"{}\"", folder.display()),
+                    err,
+                )
+            })?
+            .path();
 
-                if let Err(err) = std::os::unix::fs::chown(
-                    std::path::PathBuf::from(&config.filename),
-                    Some(501),
-                    Some(20),
-                ) {
-                    error!("Unable to change file owner", config.filename; err);
-                    return;
-                }
+        match path.is_dir() {
+            true => files.extend(walk(&path)?),
+            false => files.push(path),
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+fn message_flag() -> seahorse::Flag {
+    seahorse::Flag::new("message", seahorse::FlagType::String)
+        .description("Describe the change in the config history")
+        .alias("m")
+}
+
+/// The `--message` of a command that changes a config, or a default describing the change
+fn message(context: &seahorse::Context, default: String) -> String {
+    context.string_flag("message").unwrap_or(default)
+}
+
+fn revision(argument: Option<&String>) -> Result<i32> {
+    let argument =
+        argument.ok_or_else(|| Error::Usage("Please provide a revision number".into()))?;
+
+    argument
+        .parse()
+        .map_err(|_| Error::Usage(format!("Invalid revision \"{}\"", argument)))
+}
+
+fn force_flag() -> seahorse::Flag {
+    seahorse::Flag::new("force", seahorse::FlagType::Bool)
+        .description("Update stored configs that differ without asking")
+}
+
+/// Replaces stored configs with the content of the files they are read from, confirming every
+/// change
+fn update_from(
+    context: &seahorse::Context,
+    sources: Vec<(Config, std::path::PathBuf, String)>,
+) -> Result<()> {
+    let force = context.bool_flag("force");
+    let mut refused = 0;
+
+    for (config, source, local) in sources {
+        // A file cloned from a template differs from it only by the values of its variables,
+        // storing it would replace the template with the rendered file
+        if template::matches(&config.content, &local) {
+            success!("Already up to date", config.path);
+            continue;
+        }
 
-                success!("Cloned file", config.filename);
+        if !force {
+            if !prompt::interactive() {
+                warn!(
+                    "Refused to update config from changed file",
+                    source.display()
+                );
+                refused += 1;
+                continue;
             }
 
-            if context.args.is_empty() {
-                error!("Please provide some shorthands to clone");
+            print!(
+                "{}",
+                diff::unified(
+                    &config.content,
+                    &local,
+                    &format!("{} (stored)", config.path),
+                    &format!("{} (local)", source.display()),
+                )
+            );
+            let question = format!(
+                "Update \"{}\" of config \"{}\"?",
+                config.path, config.shorthand
+            );
+            if prompt::choose(&question, &["yes", "no"])? == 'n' {
+                warn!("Kept stored config", config.path);
+                continue;
             }
-        })
+        }
+
+        diesel::update(configs::dsl::configs)
+            .filter(configs::shorthand.eq(&config.shorthand))
+            .filter(configs::path.eq(&config.path))
+            .set(configs::content.eq(&local))
+            .execute(&mut crate::connect_db()?)
+            .map_err(|err| {
+                Error::Database(
+                    format!("Unable to update config \"{}\"", config.shorthand),
+                    err,
+                )
+            })?;
+
+        history::record_config(
+            &mut crate::connect_db()?,
+            &config.shorthand,
+            &message(
+                context,
+                format!("Updated \"{}\" from local file", config.path),
+            ),
+        )?;
+
+        success!("Updated config", config.path);
+    }
+
+    if refused > 0 {
+        return Err(Error::Conflict(format!(
+            "{} config(s) were not updated, use --force to update them",
+            refused
+        )));
+    }
+
+    Ok(())
 }
 
-fn vim() -> seahorse::Command {
-    seahorse::Command::new("vim")
-        .description("View a project configuration file in Vim")
-        .usage("nova configs vim [shorthand]")
+fn list() -> seahorse::Command {
+    seahorse::Command::new("list")
+        .description("List all project configuration file(s) and their shorthands")
+        .usage("nova configs list")
         .action(|context| {
-            let shorthand = match context.args.first() {
-                Some(shorthand) => shorthand,
-                None => {
-                    error!("Please provide a filename to edit");
-                    return;
-                }
-            };
+            crate::run(context, |_| {
+                let configs = configs::dsl::configs
+                    .load::<Config>(&mut crate::connect_db()?)
+                    .map_err(|err| Error::Database("Unable to fetch configs".into(), err))?;
 
-            let config = match configs::dsl::configs
-                .filter(configs::shorthand.eq(shorthand))
-                .first::<Config>(&mut crate::connect_db())
-            {
-                Ok(config) => config,
-                Err(_) => {
-                    error!("Unknown config shorthand", shorthand);
-                    return;
-                }
-            };
+                let records = configs
+                    .into_iter()
+                    .map(|config| Record {
+                        size: config.content.len(),
+                        shorthand: config.shorthand,
+                        filename: config.path,
+                        ..Default::default()
+                    })
+                    .collect::<Vec<_>>();
 
-            let path = std::path::PathBuf::from(format!("/Users/mac/TEMP_{}", &config.filename));
-            if let Err(err) = std::fs::write(&path, &config.content) {
-                error!("Unable to write to temp file", config.filename; err);
-                return;
-            }
+                output::print_records(
+                    &[
+                        ("Shorthand", Field::Shorthand),
+                        ("Path", Field::Filename),
+                        ("Content Length", Field::Size),
+                    ],
+                    &records,
+                )?;
+                Ok(())
+            })
+        })
+}
 
-            let mut child = match std::process::Command::new("/opt/homebrew/bin/nvim")
-                .arg(&path.to_str().unwrap())
-                .spawn()
-            {
-                Ok(child) => child,
-                Err(err) => {
-                    error!("Unable to run nvim"; err);
-                    return;
+fn clone() -> seahorse::Command {
+    Overwrite::flags(
+        seahorse::Command::new("clone")
+            .description("Clone project configuration file(s) to the current working directory")
+            .usage("nova configs clone [...shorthands|@bundles] [--var key=value]... [flags]"),
+    )
+    .flag(
+        seahorse::Flag::new("merge", seahorse::FlagType::Bool)
+            .description("Deep merge JSON, YAML and TOML configs into existing files"),
+    )
+    .flag(
+        seahorse::Flag::new("prefer", seahorse::FlagType::String)
+            .description("Side to keep values from when merging: stored (default) or local"),
+    )
+    .action(|context| {
+        crate::run(context, |context| {
+            let mut shorthands = context.args.clone();
+            let mut variables = Variables::take(&mut shorthands)?;
+            let mut overwrite = Overwrite::new(context);
+            let merging = context.bool_flag("merge");
+            let prefer_local = match context.string_flag("prefer") {
+                Ok(prefer) if prefer == "local" => true,
+                Ok(prefer) if prefer == "stored" => false,
+                Ok(prefer) => {
+                    return Err(Error::Usage(format!(
+                        "Unknown side to prefer \"{}\", expected local or stored",
+                        prefer
+                    )))
                 }
+                Err(_) => false,
             };
 
-            match child.wait() {
-                Ok(_) => {}
-                Err(err) => {
-                    error!("Nvim returned a non-zero status"; err);
-                    return;
-                }
+            if shorthands.is_empty() {
+                return Err(Error::Usage(
+                    "Please provide some shorthands to clone".into(),
+                ));
             }
+            let shorthands = bundles::expand(&shorthands)?;
 
-            let content = match std::fs::read_to_string(&path) {
-                Ok(content) => content,
-                Err(err) => {
-                    error!("Unable to read from temp file", config.filename; err);
-                    return;
-                }
-            };
+            let configs = shorthands
+                .iter()
+                .map(|shorthand| find(shorthand))
+                .collect::<Result<Vec<_>>>()?
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
 
-            if let Err(err) = std::fs::remove_file(path) {
-                error!("Unable to remove temp file", config.filename; err);
-                return;
-            }
+            let mut rendered = vec![];
+            for config in &configs {
+                let prompts = match &config.variables {
+                    Some(metadata) => template::parse_prompts(metadata)?,
+                    None => Prompts::new(),
+                };
 
-            if &content == &config.content {
-                warn!("No changes made to file", config.filename);
-                return;
+                rendered.push(template::render(
+                    &config.content,
+                    &config.path,
+                    &prompts,
+                    &mut variables,
+                )?);
             }
 
-            match diesel::update(configs::dsl::configs)
-                .filter(configs::shorthand.eq(&shorthand))
-                .set(configs::content.eq(&content))
-                .execute(&mut crate::connect_db())
-            {
-                Ok(_) => {
-                    success!("Updated config", &config.filename);
-                }
-                Err(err) => {
-                    error!("Unable to update config", &config.filename; err);
+            for (config, content) in configs.iter().zip(rendered) {
+                let path = std::path::PathBuf::from(&config.path);
+                let merged = match std::fs::read_to_string(&path) {
+                    Ok(local) if merging => {
+                        Some(merge::merge(&content, &local, &config.path, prefer_local)?)
+                    }
+                    _ => None,
+                };
+
+                let outcome =
+                    overwrite.write(&path, &config.path, merged.as_ref().unwrap_or(&content))?;
+
+                match outcome {
+                    _ if overwrite.dry_run() => {}
+                    Outcome::Unchanged => success!("Already up to date", config.path),
+                    Outcome::Created | Outcome::Changed if merged.is_some() => {
+                        success!("Merged file", config.path)
+                    }
+                    Outcome::Created | Outcome::Changed => {
+                        success!("Cloned file", config.path)
+                    }
+                    Outcome::Kept | Outcome::Refused => {}
                 }
             }
+
+            overwrite.finish()
+        })
+    })
+}
+
+fn edit() -> seahorse::Command {
+    seahorse::Command::new("edit")
+        .description(
+            "Edit a project configuration file in $VISUAL, $EDITOR or the configured editor",
+        )
+        .usage("nova configs edit [shorthand] [path] [--message text]")
+        .flag(message_flag())
+        .action(|context| {
+            crate::run(context, |context| {
+                let shorthand = context
+                    .args
+                    .first()
+                    .ok_or_else(|| Error::Usage("Please provide a shorthand to edit".into()))?;
+
+                let config = find_one(shorthand, context.args.get(1))?;
+
+                let filename = std::path::Path::new(&config.path)
+                    .file_name()
+                    .unwrap()
+                    .to_str()
+                    .unwrap();
+                let content = editor::edit(&config.content, filename)?;
+
+                if content == config.content {
+                    warn!("No changes made to file", config.path);
+                    return Ok(());
+                }
+
+                diesel::update(configs::dsl::configs)
+                    .filter(configs::shorthand.eq(&shorthand))
+                    .filter(configs::path.eq(&config.path))
+                    .set(configs::content.eq(&content))
+                    .execute(&mut crate::connect_db()?)
+                    .map_err(|err| {
+                        Error::Database(format!("Unable to update config \"{}\"", config.path), err)
+                    })?;
+
+                history::record_config(
+                    &mut crate::connect_db()?,
+                    shorthand,
+                    &message(context, format!("Edited \"{}\"", config.path)),
+                )?;
+
+                success!("Updated config", &config.path);
+                Ok(())
+            })
         })
 }
 
 fn add() -> seahorse::Command {
     seahorse::Command::new("add")
-        .description("Add a new configuration file, uses file content if the file exists")
-        .usage("nova configs add [shorthand] [filename]")
+        .description("Add a file, or every file in a folder, to a config shorthand")
+        .usage("nova configs add [shorthand] [path/to/file/or/folder] [--message text]")
+        .flag(message_flag())
         .action(|context| {
-            let shorthand = match context.args.first() {
-                Some(shorthand) => shorthand,
-                None => {
-                    error!("Please provide a shorthand, then a filename");
-                    return;
+            crate::run(context, |context| {
+                let shorthand = context.args.first().ok_or_else(|| {
+                    Error::Usage("Please provide a shorthand, then a path".into())
+                })?;
+
+                let local = std::path::PathBuf::from(
+                    context
+                        .args
+                        .get(1)
+                        .ok_or_else(|| Error::Usage("Please provide a path".into()))?,
+                );
+
+                let mut files = vec![];
+                if local.is_dir() {
+                    for file in walk(&local)? {
+                        let content = std::fs::read_to_string(&file).map_err(|err| {
+                            Error::Io(
+                                format!("Unable to read from file \"{}\"", file.display()),
+                                err,
+                            )
+                        })?;
+                        files.push((target_path(file.to_str().unwrap())?, content));
+                    }
+                } else {
+                    let path = target_path(local.to_str().unwrap())?;
+                    let content = match std::fs::read_to_string(&local) {
+                        Ok(content) => content,
+                        Err(_) => {
+                            warn!("Could not read file data", local.display());
+                            String::new()
+                        }
+                    };
+                    files.push((path, content));
                 }
-            };
 
-            let filename = match context.args.get(1) {
-                Some(filename) => filename,
-                None => {
-                    error!("Please provide a filename");
-                    return;
+                if files.is_empty() {
+                    return Err(Error::Usage(format!(
+                        "Folder \"{}\" has no files to add",
+                        local.display()
+                    )));
                 }
-            };
 
-            let content = match std::fs::read_to_string(std::path::PathBuf::from(&filename)) {
-                Ok(content) => content,
-                Err(_) => {
-                    warn!("Could not read file data", filename);
-                    String::new()
+                let existing = configs::dsl::configs
+                    .filter(configs::shorthand.eq(shorthand))
+                    .load::<Config>(&mut crate::connect_db()?)
+                    .map_err(|err| Error::Database("Unable to fetch configs".into(), err))?;
+                if let Some((path, _)) = files
+                    .iter()
+                    .find(|(path, _)| existing.iter().any(|config| config.path == *path))
+                {
+                    return Err(Error::Conflict(format!(
+                        "Config \"{}\" already has file \"{}\"",
+                        shorthand, path
+                    )));
                 }
-            };
 
-            match configs::dsl::configs
-                .filter(configs::shorthand.eq(shorthand))
-                .count()
-                .get_result::<i64>(&mut crate::connect_db())
-            {
-                Ok(configs) => {
-                    if configs != 0 {
-                        error!("Shorthand already exists");
-                        return;
+                let variables = existing.into_iter().find_map(|config| config.variables);
+                let configs = files
+                    .into_iter()
+                    .map(|(path, content)| Config {
+                        shorthand: shorthand.to_string(),
+                        path,
+                        content,
+                        variables: variables.clone(),
+                    })
+                    .collect::<Vec<_>>();
+
+                diesel::insert_into(configs::dsl::configs)
+                    .values(&configs)
+                    .execute(&mut crate::connect_db()?)
+                    .map_err(|err| {
+                        Error::Database(
+                            format!("Unable to store new config \"{}\"", shorthand),
+                            err,
+                        )
+                    })?;
+
+                let paths = configs
+                    .iter()
+                    .map(|config| config.path.as_str())
+                    .collect::<Vec<_>>()
+                    .join("\", \"");
+                history::record_config(
+                    &mut crate::connect_db()?,
+                    shorthand,
+                    &message(context, format!("Added \"{}\"", paths)),
+                )?;
+
+                success!(format!(
+                    "Added config \"{shorthand}\" which expands to \"{paths}\""
+                ));
+                Ok(())
+            })
+        })
+}
+
+fn vars() -> seahorse::Command {
+    seahorse::Command::new("vars")
+        .description("Show or set how template variables of a config are prompted for")
+        .usage("nova configs vars [shorthand] [path/to/variables.toml]")
+        .action(|context| {
+            crate::run(context, |context| {
+                let shorthand = context
+                    .args
+                    .first()
+                    .ok_or_else(|| Error::Usage("Please provide a shorthand".into()))?;
+
+                let config = find(shorthand)?.remove(0);
+
+                let path = match context.args.get(1) {
+                    Some(path) => path,
+                    None => {
+                        println!("{}", config.variables.unwrap_or_default().trim_end());
+                        return Ok(());
                     }
+                };
+
+                let metadata = std::fs::read_to_string(path).map_err(|err| {
+                    Error::Io(format!("Unable to read from file \"{}\"", path), err)
+                })?;
+                template::parse_prompts(&metadata)?;
+
+                diesel::update(configs::dsl::configs)
+                    .filter(configs::shorthand.eq(&shorthand))
+                    .set(configs::variables.eq(Some(&metadata).filter(|m| !m.trim().is_empty())))
+                    .execute(&mut crate::connect_db()?)
+                    .map_err(|err| {
+                        Error::Database(format!("Unable to update config \"{}\"", shorthand), err)
+                    })?;
+
+                success!("Updated variables of config", shorthand);
+                Ok(())
+            })
+        })
+}
+
+fn remove() -> seahorse::Command {
+    seahorse::Command::new("remove")
+        .description("Remove a configuration, or one of its files")
+        .usage("nova configs remove [shorthand] [path] [--message text]")
+        .flag(message_flag())
+        .action(|context| {
+            crate::run(context, |context| {
+                let shorthand = context
+                    .args
+                    .first()
+                    .ok_or_else(|| Error::Usage("Please provide a shorthand".into()))?;
+
+                if let Some(path) = context.args.get(1) {
+                    let config = find_one(shorthand, Some(path))?;
+                    diesel::delete(configs::dsl::configs)
+                        .filter(configs::shorthand.eq(&shorthand))
+                        .filter(configs::path.eq(&config.path))
+                        .execute(&mut crate::connect_db()?)
+                        .map_err(|err| {
+                            Error::Database(
+                                format!("Unable to delete config \"{}\"", shorthand),
+                                err,
+                            )
+                        })?;
+
+                    history::record_config(
+                        &mut crate::connect_db()?,
+                        shorthand,
+                        &message(context, format!("Removed \"{}\"", config.path)),
+                    )?;
+
+                    success!(format!(
+                        "Removed file \"{}\" from config \"{}\"",
+                        config.path, shorthand
+                    ));
+                    return Ok(());
                 }
-                Err(err) => {
-                    error!("Unable to fetch configs"; err);
-                    return;
+
+                let deleted = diesel::delete(configs::dsl::configs)
+                    .filter(configs::shorthand.eq(&shorthand))
+                    .execute(&mut crate::connect_db()?)
+                    .map_err(|err| {
+                        Error::Database(format!("Unable to delete config \"{}\"", shorthand), err)
+                    })?;
+
+                if deleted == 0 {
+                    return Err(Error::NotFound(format!(
+                        "Unknown config shorthand \"{}\"",
+                        shorthand
+                    )));
                 }
-            };
 
-            match configs::dsl::configs
-                .filter(configs::filename.eq(filename))
-                .count()
-                .get_result::<i64>(&mut crate::connect_db())
-            {
-                Ok(configs) => {
-                    if configs != 0 {
-                        error!("Filename already exists");
-                        return;
+                history::record_config(
+                    &mut crate::connect_db()?,
+                    shorthand,
+                    &message(context, "Removed config".into()),
+                )?;
+
+                success!("Removed config", shorthand);
+                Ok(())
+            })
+        })
+}
+
+fn update() -> seahorse::Command {
+    seahorse::Command::new("update")
+        .description("Update a stored configuration from the file on disk")
+        .usage("nova configs update [shorthand] [path] [--force] [--message text]")
+        .flag(force_flag())
+        .flag(message_flag())
+        .action(|context| {
+            crate::run(context, |context| {
+                let shorthand = context
+                    .args
+                    .first()
+                    .ok_or_else(|| Error::Usage("Please provide a shorthand".into()))?;
+                let mut configs = find(shorthand)?;
+
+                let sources = match context.args.get(1) {
+                    None => configs
+                        .into_iter()
+                        .map(|config| {
+                            let source = std::path::PathBuf::from(&config.path);
+                            (config, source)
+                        })
+                        .collect::<Vec<_>>(),
+                    Some(path) => {
+                        let target = target_path(path).ok();
+                        let index = configs
+                            .iter()
+                            .position(|config| Some(&config.path) == target.as_ref())
+                            .or((configs.len() == 1).then_some(0))
+                            .ok_or_else(|| {
+                                Error::NotFound(format!(
+                                    "Config \"{}\" has no file \"{}\"",
+                                    shorthand, path
+                                ))
+                            })?;
+                        vec![(configs.remove(index), std::path::PathBuf::from(path))]
                     }
+                };
+
+                let sources = sources
+                    .into_iter()
+                    .map(|(config, source)| {
+                        let local = std::fs::read_to_string(&source).map_err(|err| {
+                            Error::Io(
+                                format!("Unable to read from file \"{}\"", source.display()),
+                                err,
+                            )
+                        })?;
+                        Ok((config, source, local))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                update_from(context, sources)
+            })
+        })
+}
+
+fn pull_all() -> seahorse::Command {
+    seahorse::Command::new("pull-all")
+        .description("Update every stored configuration from its file in this folder")
+        .usage("nova configs pull-all [--force] [--message text]")
+        .flag(force_flag())
+        .flag(message_flag())
+        .action(|context| {
+            crate::run(context, |context| {
+                let configs = configs::dsl::configs
+                    .order((configs::path, configs::shorthand))
+                    .load::<Config>(&mut crate::connect_db()?)
+                    .map_err(|err| Error::Database("Unable to fetch configs".into(), err))?;
+
+                let mut shorthands = std::collections::HashMap::<String, Vec<String>>::new();
+                for config in &configs {
+                    shorthands
+                        .entry(config.path.clone())
+                        .or_default()
+                        .push(config.shorthand.clone());
                 }
-                Err(err) => {
-                    error!("Unable to fetch configs"; err);
-                    return;
+
+                let mut sources = vec![];
+                for config in configs {
+                    let source = std::path::PathBuf::from(&config.path);
+                    let local = match std::fs::read_to_string(&source) {
+                        Ok(local) => local,
+                        Err(_) => continue,
+                    };
+
+                    let owners = &shorthands[&config.path];
+                    if owners.len() > 1 {
+                        if owners[0] == config.shorthand {
+                            warn!(format!(
+                                "Skipped \"{}\" as it is stored by configs \"{}\"",
+                                config.path,
+                                owners.join("\", \"")
+                            ));
+                        }
+                        continue;
+                    }
+
+                    sources.push((config, source, local));
                 }
-            };
 
-            let config = Config {
-                filename: filename.to_string(),
-                shorthand: shorthand.to_string(),
-                content,
-            };
+                update_from(context, sources)
+            })
+        })
+}
 
-            match diesel::insert_into(configs::dsl::configs)
-                .values(&config)
-                .execute(&mut crate::connect_db())
-            {
-                Ok(_) => {
-                    success!(format!(
-                        "Added config \"{shorthand}\" which expands to \"{filename}\""
-                    ))
+fn status() -> seahorse::Command {
+    seahorse::Command::new("status")
+        .description("Check if the configuration files here are behind the stored versions")
+        .usage("nova configs status")
+        .action(|context| {
+            crate::run(context, |_| {
+                let connection = &mut crate::connect_db()?;
+                let configs = configs::dsl::configs
+                    .order((configs::path, configs::shorthand))
+                    .load::<Config>(connection)
+                    .map_err(|err| Error::Database("Unable to fetch configs".into(), err))?;
+
+                let mut records = vec![];
+                for config in configs {
+                    let local = match std::fs::read_to_string(&config.path) {
+                        Ok(local) => local,
+                        Err(_) => continue,
+                    };
+
+                    let status = if template::matches(&config.content, &local) {
+                        "identical"
+                    } else if history::config_file_log(connection, &config.shorthand, &config.path)?
+                        .iter()
+                        .any(|version| template::matches(&version.content, &local))
+                    {
+                        "behind"
+                    } else {
+                        "diverged"
+                    };
+
+                    records.push(Record {
+                        shorthand: config.shorthand,
+                        filename: config.path,
+                        status: status.into(),
+                        ..Default::default()
+                    });
                 }
-                Err(err) => {
-                    error!("Unable to store new config", shorthand; err);
+
+                output::print_records(
+                    &[
+                        ("Shorthand", Field::Shorthand),
+                        ("Path", Field::Filename),
+                        ("Status", Field::Status),
+                    ],
+                    &records,
+                )?;
+
+                if settings::get().format == Format::Table {
+                    let count = |status: &str| {
+                        records
+                            .iter()
+                            .filter(|record| record.status == status)
+                            .count()
+                    };
+                    println!(
+                        "\n{} identical, {} behind, {} diverged",
+                        count("identical"),
+                        count("behind"),
+                        count("diverged")
+                    );
                 }
-            }
+
+                Ok(())
+            })
         })
 }
 
-fn remove() -> seahorse::Command {
-    seahorse::Command::new("remove")
-        .description("Remove a configuration file")
-        .usage("nova configs remove [shorthand]")
+fn log() -> seahorse::Command {
+    seahorse::Command::new("log")
+        .description("List the revisions of a configuration")
+        .usage("nova configs log [shorthand]")
         .action(|context| {
-            let shorthand = match context.args.first() {
-                Some(shorthand) => shorthand,
-                None => {
-                    error!("Please provide a shorthand");
-                    return;
+            crate::run(context, |context| {
+                let shorthand = context
+                    .args
+                    .first()
+                    .ok_or_else(|| Error::Usage("Please provide a shorthand".into()))?;
+
+                let versions = history::config_log(&mut crate::connect_db()?, shorthand)?;
+                if versions.is_empty() {
+                    return Err(Error::NotFound(format!(
+                        "Unknown config shorthand \"{}\"",
+                        shorthand
+                    )));
                 }
-            };
 
-            match diesel::delete(configs::dsl::configs)
-                .filter(configs::shorthand.eq(&shorthand))
-                .execute(&mut crate::connect_db())
-            {
-                Ok(deleted) => {
-                    if deleted == 0 {
-                        error!("Unknown config shorthand", shorthand);
-                    } else {
-                        success!("Removed config", shorthand);
+                let records = versions
+                    .into_iter()
+                    .map(|version| Record {
+                        revision: version.revision,
+                        date: time::datetime(version.created_at),
+                        message: version.message,
+                        ..Default::default()
+                    })
+                    .collect::<Vec<_>>();
+
+                output::print_records(
+                    &[
+                        ("Revision", Field::Revision),
+                        ("Date", Field::Date),
+                        ("Message", Field::Message),
+                    ],
+                    &records,
+                )?;
+                Ok(())
+            })
+        })
+}
+
+fn diff() -> seahorse::Command {
+    seahorse::Command::new("diff")
+        .description(
+            "Show changes to a configuration since a revision, defaults to the last change",
+        )
+        .usage("nova configs diff [shorthand] [revision]")
+        .action(|context| {
+            crate::run(context, |context| {
+                let shorthand = context
+                    .args
+                    .first()
+                    .ok_or_else(|| Error::Usage("Please provide a shorthand".into()))?;
+
+                let connection = &mut crate::connect_db()?;
+                let latest = history::config_log(connection, shorthand)?
+                    .first()
+                    .map(|version| version.revision)
+                    .ok_or_else(|| {
+                        Error::NotFound(format!("Unknown config shorthand \"{}\"", shorthand))
+                    })?;
+
+                let revision = match context.args.get(1) {
+                    Some(_) => revision(context.args.get(1))?,
+                    None if latest > 1 => latest - 1,
+                    None => {
+                        return Err(Error::NotFound(format!(
+                            "Config \"{}\" has no earlier revision to compare with",
+                            shorthand
+                        )))
+                    }
+                };
+
+                let old = history::config_revision(connection, shorthand, revision)?
+                    .into_iter()
+                    .map(|version| (version.path, version.content))
+                    .collect::<std::collections::BTreeMap<_, _>>();
+                let new = configs::dsl::configs
+                    .filter(configs::shorthand.eq(shorthand))
+                    .load::<Config>(connection)
+                    .map_err(|err| Error::Database("Unable to fetch configs".into(), err))?
+                    .into_iter()
+                    .map(|config| (config.path, config.content))
+                    .collect::<std::collections::BTreeMap<_, _>>();
+
+                let paths = old
+                    .keys()
+                    .chain(new.keys())
+                    .collect::<std::collections::BTreeSet<_>>();
+                let mut changed = false;
+                for path in paths {
+                    let before = old.get(path).map(String::as_str).unwrap_or_default();
+                    let after = new.get(path).map(String::as_str).unwrap_or_default();
+                    if old.get(path) != new.get(path) {
+                        changed = true;
+                        print!(
+                            "{}",
+                            diff::unified(
+                                before,
+                                after,
+                                &format!("{} (revision {})", path, revision),
+                                &format!("{} (current)", path),
+                            )
+                        );
                     }
                 }
-                Err(err) => {
-                    error!("Unable to delete config", shorthand; err);
+
+                if !changed {
+                    println!("No changes since revision {}", revision);
                 }
-            };
+
+                Ok(())
+            })
+        })
+}
+
+fn rollback() -> seahorse::Command {
+    seahorse::Command::new("rollback")
+        .description("Restore the files of a configuration to how they were at a revision")
+        .usage("nova configs rollback [shorthand] [revision] [--message text]")
+        .flag(message_flag())
+        .action(|context| {
+            crate::run(context, |context| {
+                let shorthand = context
+                    .args
+                    .first()
+                    .ok_or_else(|| Error::Usage("Please provide a shorthand".into()))?;
+                let revision = revision(context.args.get(1))?;
+
+                let connection = &mut crate::connect_db()?;
+                let versions = history::config_revision(connection, shorthand, revision)?;
+
+                let variables = configs::dsl::configs
+                    .filter(configs::shorthand.eq(shorthand))
+                    .select(configs::variables)
+                    .load::<Option<String>>(connection)
+                    .map_err(|err| Error::Database("Unable to fetch configs".into(), err))?
+                    .into_iter()
+                    .flatten()
+                    .next();
+                let configs = versions
+                    .into_iter()
+                    .map(|version| Config {
+                        shorthand: version.shorthand,
+                        path: version.path,
+                        content: version.content,
+                        variables: variables.clone(),
+                    })
+                    .collect::<Vec<_>>();
+
+                connection
+                    .transaction::<_, diesel::result::Error, _>(|connection| {
+                        diesel::delete(configs::dsl::configs)
+                            .filter(configs::shorthand.eq(shorthand))
+                            .execute(connection)?;
+                        diesel::insert_into(configs::dsl::configs)
+                            .values(&configs)
+                            .execute(connection)?;
+                        Ok(())
+                    })
+                    .map_err(|err| {
+                        Error::Database(
+                            format!("Unable to roll back config \"{}\"", shorthand),
+                            err,
+                        )
+                    })?;
+
+                history::record_config(
+                    connection,
+                    shorthand,
+                    &message(context, format!("Rolled back to revision {}", revision)),
+                )?;
+
+                success!(format!(
+                    "Rolled back config \"{}\" to revision {}",
+                    shorthand, revision
+                ));
+                Ok(())
+            })
         })
 }
 
@@ -272,8 +960,16 @@ pub fn configs() -> seahorse::Command {
         .description("Manage reusable project configuration files")
         .command(list())
         .command(clone())
-        .command(vim())
+        .command(edit())
         .command(add())
+        .command(vars())
         .command(remove())
-        .action(|context| context.help())
+        .command(update())
+        .command(pull_all())
+        .command(status())
+        .command(log())
+        .command(diff())
+        .command(rollback())
+        .command(bundles::bundle())
+        .action(crate::help)
 }
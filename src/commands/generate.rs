@@ -1,240 +1,170 @@
 use {
-    crate::{error, success},
+    crate::{
+        error::{Error, Result},
+        manifest, output, success,
+    },
     clipboard::{ClipboardContext, ClipboardProvider},
 };
 
-fn using_clean_url<T>(text: T) -> String
-where
-    T: Into<String>,
-{
-    urlencoding::encode(&text.into())
-        .replace('-', "--")
-        .replace('_', "__")
-}
+static README_START: &str = "<!-- nova:built-with:start -->";
+static README_END: &str = "<!-- nova:built-with:end -->";
 
-fn read_package_json(text: String) -> Option<()> {
-    let mut output = String::new();
-
-    let json = serde_json::from_str::<serde_json::Value>(&text).ok()?;
-
-    let empty_object = serde_json::Value::Object(serde_json::map::Map::new());
-    let dependencies = json
-        .as_object()?
-        .get("dependencies")?
-        .as_object()?
-        .iter()
-        .chain(
-            json.as_object()?
-                .get("devDependencies")
-                .unwrap_or(&empty_object)
-                .as_object()?
-                .iter(),
-        )
-        .map(|(k, v)| (k.as_str(), v.as_str().unwrap()))
-        .collect::<std::collections::HashMap<_, _>>();
-
-    let mut dependency_names = dependencies.keys().collect::<Vec<_>>();
-    dependency_names.sort();
-
-    for dependency in dependency_names {
-        let dependency = *dependency;
-        let version = *dependencies.get(dependency).unwrap();
-
-        output.push_str(&format!("        -   [![{}](https://img.shields.io/badge/{}-{}-red?style=flat-square)](https://npmjs.com/package/{}/v/{})\n",
-			dependency,
-			using_clean_url(dependency),
-			using_clean_url(version),
-			dependency,
-			version.replace(['^', '~'], "")
-		));
-    }
-
-    let mut ctx: ClipboardContext = ClipboardProvider::new().unwrap();
-    ctx.set_contents(output[..output.len() - 1].into()).unwrap();
-    ctx.get_contents().unwrap();
-    success!("Copied package.json data to clipboard");
-    Some(())
-}
+fn copy_to_clipboard(output: &str) -> Result<()> {
+    let mut context: ClipboardContext = ClipboardProvider::new().map_err(|err| {
+        Error::Process(format!(
+            "Unable to access the clipboard, try --output stdout\n{}",
+            err
+        ))
+    })?;
 
-fn read_pubspec_yaml(text: String) -> Option<()> {
-    let mut output = String::new();
-
-    let yaml = serde_yaml::from_str::<serde_yaml::Value>(&text).ok()?;
-
-    let dependencies = yaml
-        .as_mapping()?
-        .get("dependencies")?
-        .as_mapping()?
-        .iter()
-        .chain(
-            yaml.as_mapping()?
-                .get("dev_dependencies")?
-                .as_mapping()?
-                .iter(),
-        )
-        .filter(|(_, v)| v.is_string())
-        .map(|(k, v)| (k.as_str().unwrap(), v.as_str().unwrap()))
-        .collect::<std::collections::HashMap<_, _>>();
-
-    let mut dependency_names = dependencies.keys().collect::<Vec<_>>();
-    dependency_names.sort();
-
-    for dependency in dependency_names {
-        let dependency = *dependency;
-        let version = *dependencies.get(dependency).unwrap();
-
-        output.push_str(&format!("        -   [![{}](https://img.shields.io/badge/{}-{}-blue?style=flat-square)](https://pub.dev/packages/{}/versions/{})\n",
-			dependency,
-			using_clean_url(dependency),
-			using_clean_url(version),
-			dependency,
-			version.replace(['^', '~'], ""),
-		));
-    }
-
-    let mut ctx: ClipboardContext = ClipboardProvider::new().unwrap();
-    ctx.set_contents(output[..output.len() - 1].into()).unwrap();
-    ctx.get_contents().unwrap();
-    Some(())
-}
+    context
+        .set_contents(output.into())
+        .and_then(|_| context.get_contents())
+        .map_err(|err| Error::Process(format!("Unable to copy to the clipboard\n{}", err)))?;
 
-fn read_cargo_toml(text: String) -> Option<()> {
-    let mut output = String::new();
-
-    let cargo = text.parse::<toml::Value>().ok()?;
-
-    let dependencies = cargo.get("dependencies")?.as_table()?;
-    for (dependency, version) in dependencies {
-        let version = if version.is_str() {
-            version.as_str()
-        } else {
-            version.as_table()?.get("version")?.as_str()
-        }?;
-
-        output.push_str(&format!("        -   [![{}](https://img.shields.io/badge/{}-{}-yellow?style=flat-square)](https://crates.io/crates/{}/{})\n",
-			dependency,
-			using_clean_url(dependency),
-			using_clean_url(version),
-			dependency,
-			version.replace(['^', '~'], ""),
-		));
-    }
-
-    let mut ctx: ClipboardContext = ClipboardProvider::new().unwrap();
-    ctx.set_contents(output[..output.len() - 1].into()).unwrap();
-    ctx.get_contents().unwrap();
-    success!("Copied Cargo.toml data to clipboard");
-    Some(())
+    Ok(())
 }
 
-fn read_build_gradle(text: String) -> Option<()> {
-    let mut output = String::new();
-
-    let mut reading_dependencies = false;
-    for line in text.split('\n') {
-        let line = line.trim();
-
-        if reading_dependencies {
-            if line.trim() == "}" {
-                break;
+/// Replaces the lines between the markers in the README, adding the markers to the end of the
+/// "Built with" section if they are missing
+fn write_readme(path: &str, output: &str) -> Result<()> {
+    let readme = std::fs::read_to_string(path)
+        .map_err(|err| Error::Io(format!("Unable to read from file \"{}\"", path), err))?;
+    let lines = readme.lines().collect::<Vec<_>>();
+    let output = output.lines().collect::<Vec<_>>();
+
+    let start = lines.iter().position(|line| line.trim() == README_START);
+    let end = lines.iter().position(|line| line.trim() == README_END);
+    let content = match (start, end) {
+        (Some(start), Some(end)) if start < end => {
+            [&lines[..=start], &output, &lines[end..]].concat()
+        }
+        (None, None) => {
+            let heading = lines
+                .iter()
+                .position(|line| {
+                    line.starts_with('#')
+                        && line
+                            .trim_start_matches('#')
+                            .trim()
+                            .eq_ignore_ascii_case("built with")
+                })
+                .ok_or_else(|| {
+                    Error::NotFound(format!("No \"Built with\" section in \"{}\"", path))
+                })?;
+
+            let next = lines[heading + 1..]
+                .iter()
+                .position(|line| line.starts_with('#'))
+                .map_or(lines.len(), |index| heading + 1 + index);
+            let mut end = next;
+            while end > heading + 1 && lines[end - 1].trim().is_empty() {
+                end -= 1;
             }
 
-            let regex = regex::Regex::new(
-                r#"^\w+ (?:['"](.+):(.+):(.+)['"]|\w+\(['"](.+):(.+):(.+)['"]\))$"#,
-            )
-            .unwrap();
-            if regex.is_match(line) {
-                let captures = regex.captures(line).unwrap();
-                let group = captures
-                    .get(1)
-                    .unwrap_or_else(|| captures.get(4).unwrap())
-                    .as_str();
-                let dependency = captures
-                    .get(2)
-                    .unwrap_or_else(|| captures.get(5).unwrap())
-                    .as_str();
-                let version = captures
-                    .get(3)
-                    .unwrap_or_else(|| captures.get(6).unwrap())
-                    .as_str();
-
-                output.push_str(&format!("        -   [![{}:{}](https://img.shields.io/badge/{}-{}-brightgreen?style=flat-square)](https://mvnrepository.com/artifact/{}/{}/{})\n",
-				group,
-				dependency,
-				using_clean_url(format!("{}:{}", group, dependency)),
-				using_clean_url(version),
-				group,
-				dependency,
-				version,
-			));
-            } else if line.is_empty() || line.starts_with("//") {
-                continue;
-            } else {
-                error!("Failed to parse dependency", line);
-            }
-        } else if line == "dependencies {" {
-            reading_dependencies = true;
-            continue;
+            let separator: &[&str] = if next < lines.len() { &[""] } else { &[] };
+            [
+                &lines[..end],
+                &["", README_START],
+                &output,
+                &[README_END],
+                separator,
+                &lines[next..],
+            ]
+            .concat()
+        }
+        _ => {
+            return Err(Error::Parse(format!(
+                "Mismatched \"{}\" and \"{}\" markers in \"{}\"",
+                README_START, README_END, path
+            )))
         }
-    }
+    };
 
-    let mut ctx: ClipboardContext = ClipboardProvider::new().unwrap();
-    ctx.set_contents(output[..output.len() - 1].into()).unwrap();
-    ctx.get_contents().unwrap();
-    success!("Copied build.gradle data to clipboard");
-    Some(())
+    write(path, &content)
+}
+
+fn write(path: &str, lines: &[&str]) -> Result<()> {
+    std::fs::write(path, format!("{}\n", lines.join("\n")))
+        .map_err(|err| Error::Io(format!("Unable to write to file \"{}\"", path), err))
 }
 
 pub fn generate() -> seahorse::Command {
     seahorse::Command::new("generate")
         .description("Generate the `Built with` section for my README.md files")
-        .usage("nova generate [path/to/file]")
+        .usage("nova generate [path/to/file] [--output clipboard|stdout|file] [--readme path]")
+        .flag(
+            seahorse::Flag::new("output", seahorse::FlagType::String).description(
+                "Where to put the generated list: clipboard (default), stdout or file",
+            ),
+        )
+        .flag(
+            seahorse::Flag::new("file", seahorse::FlagType::String)
+                .description("File to write the generated list to with --output file"),
+        )
+        .flag(
+            seahorse::Flag::new("readme", seahorse::FlagType::String)
+                .description("Replace the generated list in the Built with section of a README"),
+        )
         .action(|context| {
-            let path = match context.args.first() {
-                Some(path) => std::path::PathBuf::from(path),
-                None => {
-                    error!("Please provide a filename");
-                    return;
-                }
-            };
+            crate::run(context, |context| {
+                let path = match context.args.first() {
+                    Some(path) => std::path::PathBuf::from(path),
+                    None => return Err(Error::Usage("Please provide a filename".into())),
+                };
+
+                let file = std::fs::read_to_string(&path).map_err(|err| {
+                    Error::Io(
+                        format!("Unable to read from file \"{}\"", path.display()),
+                        err,
+                    )
+                })?;
+
+                let filename = match path.file_name() {
+                    Some(filename) => filename.to_str().unwrap(),
+                    None => {
+                        return Err(Error::Usage(format!(
+                            "Cannot parse folder \"{}\"",
+                            path.display()
+                        )))
+                    }
+                };
 
-            let file = match std::fs::read_to_string(&path) {
-                Ok(file) => file,
-                Err(err) => {
-                    error!("Unable to read from file", path.display(); err);
-                    return;
-                }
-            };
+                let parser = manifest::parser(filename).ok_or_else(|| {
+                    Error::Usage(format!("Unable to parse file \"{}\"", path.display()))
+                })?;
 
-            if path.file_name().is_none() {
-                error!("Cannot parse folder", path.display());
-                return;
-            }
+                let output = manifest::render(&parser.parse(&file)?);
+                let output = output.as_str();
 
-            match path.file_name().unwrap().to_str().unwrap() {
-                "package.json" => {
-                    if read_package_json(file).is_none() {
-                        error!("Unable to parse package.json", path.display());
-                    }
+                if let Ok(readme) = context.string_flag("readme") {
+                    write_readme(&readme, output)?;
+                    success!(format!("Updated the Built with section of \"{}\"", readme));
+                    return Ok(());
                 }
-                "pubspec.yaml" => {
-                    if read_pubspec_yaml(file).is_none() {
-                        error!("Unable to parse pubspec.yaml", path.display());
+
+                match context.string_flag("output").as_deref() {
+                    Ok("clipboard") | Err(_) => {
+                        copy_to_clipboard(output)?;
+                        success!(format!("Copied {} data to clipboard", filename));
                     }
-                }
-                "Cargo.toml" => {
-                    if read_cargo_toml(file).is_none() {
-                        error!("Unable to parse Cargo.toml", path.display());
+                    Ok("stdout") => output::print(output)?,
+                    Ok("file") => {
+                        let file = context.string_flag("file").map_err(|_| {
+                            Error::Usage("Please provide a file to write to with --file".into())
+                        })?;
+                        write(&file, &[output])?;
+                        success!(format!("Wrote {} data to \"{}\"", filename, file));
                     }
-                }
-                "build.gradle" => {
-                    if read_build_gradle(file).is_none() {
-                        error!("Unable to parse build.gradle", path.display());
+                    Ok(output) => {
+                        return Err(Error::Usage(format!(
+                            "Unknown output \"{}\", expected clipboard, stdout or file",
+                            output
+                        )))
                     }
                 }
-                _ => {
-                    error!("Unable to parse file", path.display());
-                }
-            };
+
+                Ok(())
+            })
         })
 }
@@ -1,3 +1,4 @@
+mod bundles;
 mod configs;
 mod generate;
 mod secrets;
@@ -1,149 +1,294 @@
 use {
-    crate::{error, models::Secret, schema::secrets, success, warn},
-    diesel::prelude::*,
+    crate::{
+        crypto, diff, editor,
+        error::{Error, Result},
+        history,
+        models::Secret,
+        output::{self, Field, Format, Record},
+        overwrite::{Outcome, Overwrite},
+        project::{locate, Location},
+        prompt,
+        schema::secrets,
+        settings, success, time, warn,
+    },
+    diesel::{
+        prelude::*,
+        sql_types::{Bool, Nullable},
+        sqlite::Sqlite,
+    },
 };
 
-struct Location {
-    project: String,
-    folder: Option<String>,
+type OwnedBy = Box<dyn BoxableExpression<secrets::table, Sqlite, SqlType = Nullable<Bool>>>;
+
+/// Matches the secrets of a project by remote, falling back to rows stored by folder name
+fn owned_by(location: &Location) -> OwnedBy {
+    match &location.remote {
+        Some(remote) => Box::new(
+            secrets::remote.eq(remote.clone()).or(secrets::remote
+                .is_null()
+                .and(secrets::project.eq(location.project.clone()))),
+        ),
+        None => Box::new(secrets::project.eq(location.project.clone()).nullable()),
+    }
 }
 
-fn locate() -> Option<Location> {
-    regex::Regex::new(r#"Projects/([^/]*)/?(.+)?"#)
+/// Fetches the secrets of a project, preferring rows matched by remote over those matched by name
+fn fetch(location: &Location) -> Result<Vec<Secret>> {
+    let mut secrets = secrets::dsl::secrets
+        .filter(owned_by(location))
+        .order(secrets::remote.is_null())
+        .get_results::<Secret>(&mut crate::connect_db()?)
+        .map_err(|err| Error::Database("Unable to fetch secrets".into(), err))?;
+
+    let mut paths = std::collections::HashSet::new();
+    secrets.retain(|secret| paths.insert(secret.path.clone()));
+    Ok(secrets)
+}
+
+fn project_relative_path(location: &Location, cwd_relative_path: &str) -> String {
+    std::path::PathBuf::from(location.folder.clone().unwrap_or_default())
+        .join(cwd_relative_path)
+        .to_str()
         .unwrap()
-        .captures(
-            &std::env::current_dir()
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .replace('\\', "/"),
-        )
-        .map(|captures| Location {
-            project: captures.get(1).unwrap().as_str().into(),
-            folder: captures.get(2).map(|m| m.as_str().to_string()),
+        .into()
+}
+
+/// A stored secret alongside the file it is cloned to
+struct Comparison {
+    secret: Secret,
+    stored: String,
+    local: Option<String>,
+    absolute_path: std::path::PathBuf,
+}
+
+fn compare(location: &Location, cipher: &crypto::Cipher) -> Result<Vec<Comparison>> {
+    fetch(location)?
+        .into_iter()
+        .map(|secret| {
+            let absolute_path = location.root.join(&secret.path);
+            Ok(Comparison {
+                stored: cipher.decrypt(&secret.content)?,
+                local: std::fs::read_to_string(&absolute_path).ok(),
+                absolute_path,
+                secret,
+            })
         })
+        .collect()
+}
+
+fn mark_synced(secret: &Secret) -> Result<()> {
+    diesel::update(secrets::dsl::secrets)
+        .filter(secrets::project.eq(&secret.project))
+        .filter(secrets::path.eq(&secret.path))
+        .set(secrets::synced_at.eq(time::now()))
+        .execute(&mut crate::connect_db()?)
+        .map_err(|err| {
+            Error::Database(format!("Unable to update secret \"{}\"", secret.path), err)
+        })?;
+
+    Ok(())
+}
+
+fn pull(comparison: &Comparison) -> Result<()> {
+    std::fs::write(&comparison.absolute_path, &comparison.stored).map_err(|err| {
+        Error::Io(
+            format!("Unable to write to file \"{}\"", comparison.secret.path),
+            err,
+        )
+    })?;
+
+    mark_synced(&comparison.secret)
+}
+
+fn push(comparison: &Comparison, cipher: &crypto::Cipher) -> Result<()> {
+    let now = time::now();
+    let secret = Secret {
+        project: comparison.secret.project.clone(),
+        path: comparison.secret.path.clone(),
+        content: cipher.encrypt(comparison.local.as_deref().unwrap_or_default()),
+        remote: comparison.secret.remote.clone(),
+        updated_at: now,
+        synced_at: now,
+    };
+
+    diesel::update(secrets::dsl::secrets)
+        .filter(secrets::project.eq(&secret.project))
+        .filter(secrets::path.eq(&secret.path))
+        .set((
+            secrets::content.eq(&secret.content),
+            secrets::updated_at.eq(now),
+            secrets::synced_at.eq(now),
+        ))
+        .execute(&mut crate::connect_db()?)
+        .map_err(|err| {
+            Error::Database(format!("Unable to store secret \"{}\"", secret.path), err)
+        })?;
+
+    history::record_secret(&mut crate::connect_db()?, &secret)
+}
+
+/// Finds the stored secret of a path relative to the cwd
+fn find(location: &Location, cwd_relative_path: &str) -> Result<Secret> {
+    let path = project_relative_path(location, &cwd_relative_path.replace('\\', "/"));
+
+    fetch(location)?
+        .into_iter()
+        .find(|secret| secret.path == path)
+        .ok_or_else(|| Error::NotFound(format!("No secret found \"{}\"", path)))
+}
+
+fn revision_flag() -> seahorse::Flag {
+    seahorse::Flag::new("rev", seahorse::FlagType::Uint)
+        .description("Revision number of the secret, as listed by nova secrets log")
+}
+
+fn revision(context: &seahorse::Context) -> Result<Option<i32>> {
+    match context.uint_flag("rev") {
+        Ok(revision) => Ok(Some(revision as i32)),
+        Err(seahorse::error::FlagError::NotFound) => Ok(None),
+        Err(_) => Err(Error::Usage("Invalid revision given to \"--rev\"".into())),
+    }
 }
 
 fn list() -> seahorse::Command {
     seahorse::Command::new("list")
         .description("List all secret filenames for a repository without showing the data")
         .usage("nova secrets list")
-        .action(|_| {
-            let location = match locate() {
-                Some(location) => location,
-                None => {
-                    error!("Invalid project path");
-                    return;
-                }
-            };
-
-            let secrets = match secrets::dsl::secrets
-                .filter(secrets::project.eq(&location.project))
-                .get_results::<Secret>(&mut crate::connect_db())
-            {
-                Ok(secret) => secret,
-                Err(_) => {
-                    warn!("No secrets found for this project");
-                    return;
-                }
-            };
-
-            let mut table = prettytable::Table::new();
-            table.set_titles(prettytable::row!["Path", "Content Length"]);
+        .action(|context| {
+            crate::run(context, |_| {
+                let location =
+                    locate().ok_or_else(|| Error::NotFound("Invalid project path".into()))?;
+                let secrets = fetch(&location)?;
 
-            for secret in secrets {
-                table.add_row(prettytable::row![secret.path, secret.content.len()]);
-            }
+                let records = secrets
+                    .into_iter()
+                    .map(|secret| Record {
+                        size: crypto::plaintext_len(&secret.content),
+                        filename: secret.path,
+                        ..Default::default()
+                    })
+                    .collect::<Vec<_>>();
 
-            table.printstd();
+                output::print_records(
+                    &[("Path", Field::Filename), ("Content Length", Field::Size)],
+                    &records,
+                )?;
+                Ok(())
+            })
         })
 }
 
 fn clone() -> seahorse::Command {
-    seahorse::Command::new("clone")
-        .description("Clone the repository secrets to their original locations")
-        .usage("nova secrets clone")
-        .action(|_| {
-            let location = match locate() {
-                Some(location) => location,
-                None => {
-                    error!("Invalid project path");
-                    return;
-                }
-            };
-
-            let secrets = match secrets::dsl::secrets
-                .filter(secrets::project.eq(&location.project))
-                .get_results::<Secret>(&mut crate::connect_db())
-            {
-                Ok(secret) => secret,
-                Err(_) => {
-                    warn!("No secrets found for this project");
-                    return;
-                }
-            };
+    Overwrite::flags(
+        seahorse::Command::new("clone")
+            .description("Clone the repository secrets to their original locations")
+            .usage("nova secrets clone [--dry-run] [--force] [--backup]"),
+    )
+    .action(|context| {
+        crate::run(context, |context| {
+            let mut overwrite = Overwrite::new(context);
+            let location =
+                locate().ok_or_else(|| Error::NotFound("Invalid project path".into()))?;
+            let cipher = crypto::unlock(&mut crate::connect_db()?)?;
 
-            for secret in secrets {
-                let absolute_path = std::path::PathBuf::from("/Users/mac/Projects")
-                    .join(&secret.project)
-                    .join(&secret.path);
-
-                if let Err(err) = std::fs::write(&absolute_path, &secret.content) {
-                    error!("Unable to write to file", &secret.path; err);
-                    return;
+            for comparison in compare(&location, &cipher)? {
+                let path = &comparison.secret.path;
+                let outcome =
+                    overwrite.write(&comparison.absolute_path, path, &comparison.stored)?;
+                if overwrite.dry_run() {
+                    continue;
                 }
 
-                if let Err(err) = std::os::unix::fs::chown(&absolute_path, Some(501), Some(20)) {
-                    error!("Unable to change file owner", &secret.path; err);
-                    return;
+                match outcome {
+                    Outcome::Created | Outcome::Changed => {
+                        mark_synced(&comparison.secret)?;
+                        success!("Cloned secret", path);
+                    }
+                    Outcome::Unchanged => {
+                        mark_synced(&comparison.secret)?;
+                        success!("Already up to date", path);
+                    }
+                    Outcome::Kept | Outcome::Refused => {}
                 }
-
-                success!("Cloned secret", &secret.path);
             }
+
+            overwrite.finish()
         })
+    })
 }
 
 fn check() -> seahorse::Command {
     seahorse::Command::new("check")
         .description("Check if the secrets are still the same as that in the database")
-        .usage("nova secrets check")
-        .action(|_| {
-            let location = match locate() {
-                Some(location) => location,
-                None => {
-                    error!("Invalid project path");
-                    return;
-                }
-            };
-
-            let secrets = match secrets::dsl::secrets
-                .filter(secrets::project.eq(&location.project))
-                .get_results::<Secret>(&mut crate::connect_db())
-            {
-                Ok(secret) => secret,
-                Err(_) => {
-                    warn!("No secrets found for this project");
-                    return;
-                }
-            };
-
-            for secret in secrets {
-                let absolute_path = std::path::PathBuf::from("/Users/mac/Projects")
-                    .join(&secret.project)
-                    .join(&secret.path);
-                match std::fs::read_to_string(&absolute_path) {
-                    Ok(content) => {
-                        if content == secret.content {
-                            println!("Identical secret \"{}\"", &secret.path);
-                        } else {
-                            println!("Non-identical secret \"{}\"", &secret.path);
+        .usage("nova secrets check [--diff]")
+        .flag(
+            seahorse::Flag::new("diff", seahorse::FlagType::Bool)
+                .description("Show a unified diff for every non-identical secret"),
+        )
+        .action(|context| {
+            crate::run(context, |context| {
+                let location =
+                    locate().ok_or_else(|| Error::NotFound("Invalid project path".into()))?;
+                let cipher = crypto::unlock(&mut crate::connect_db()?)?;
+
+                let mut records = vec![];
+                let mut diffs = vec![];
+                for comparison in compare(&location, &cipher)? {
+                    let status = match &comparison.local {
+                        Some(local) if *local == comparison.stored => "identical",
+                        Some(local) => {
+                            diffs.push(diff::unified(
+                                &comparison.stored,
+                                local,
+                                &format!("{} (stored)", comparison.secret.path),
+                                &format!("{} (local)", comparison.secret.path),
+                            ));
+                            "non-identical"
                         }
-                    }
-                    Err(_) => {
-                        println!("Non-existent secret \"{}\"", &secret.path);
+                        None => "non-existent",
+                    };
+
+                    records.push(Record {
+                        filename: comparison.secret.path,
+                        size: comparison.stored.len(),
+                        status: status.into(),
+                        ..Default::default()
+                    });
+                }
+
+                output::print_records(
+                    &[
+                        ("Path", Field::Filename),
+                        ("Content Length", Field::Size),
+                        ("Status", Field::Status),
+                    ],
+                    &records,
+                )?;
+
+                let format = settings::get().format;
+                if context.bool_flag("diff") && format != Format::Json {
+                    for diff in diffs {
+                        print!("\n{}", diff);
                     }
                 }
-            }
+
+                if format == Format::Table {
+                    let count = |status: &str| {
+                        records
+                            .iter()
+                            .filter(|record| record.status == status)
+                            .count()
+                    };
+                    println!(
+                        "\n{} identical, {} non-identical, {} non-existent",
+                        count("identical"),
+                        count("non-identical"),
+                        count("non-existent")
+                    );
+                }
+
+                Ok(())
+            })
         })
 }
 
@@ -152,110 +297,392 @@ fn set() -> seahorse::Command {
         .description("Set a repository secret, update if it already exists")
         .usage("nova secrets set [path/to/config]")
         .action(|context| {
-            let location = match locate() {
-                Some(location) => location,
-                None => {
-                    error!("Invalid project path");
-                    return;
-                }
-            };
+            crate::run(context, |context| {
+                let location =
+                    locate().ok_or_else(|| Error::NotFound("Invalid project path".into()))?;
+
+                let cwd_relative_path = match context.args.first() {
+                    Some(path) => path.to_string().replace('\\', "/"),
+                    None => {
+                        return Err(Error::Usage(
+                            "Please provide a path to the secret file".into(),
+                        ))
+                    }
+                };
+
+                let content = std::fs::read_to_string(&cwd_relative_path).map_err(|err| {
+                    Error::Io(
+                        format!("Unable to read from file \"{}\"", cwd_relative_path),
+                        err,
+                    )
+                })?;
+
+                let cipher = crypto::unlock(&mut crate::connect_db()?)?;
+
+                let now = time::now();
+                let secret = Secret {
+                    project: location.key(),
+                    path: project_relative_path(&location, &cwd_relative_path),
+                    content: cipher.encrypt(&content),
+                    remote: location.remote.clone(),
+                    updated_at: now,
+                    synced_at: now,
+                };
+
+                diesel::insert_into(secrets::dsl::secrets)
+                    .values(&secret)
+                    .on_conflict((secrets::project, secrets::path))
+                    .do_update()
+                    .set((
+                        secrets::content.eq(&secret.content),
+                        secrets::updated_at.eq(now),
+                        secrets::synced_at.eq(now),
+                    ))
+                    .execute(&mut crate::connect_db()?)
+                    .map_err(|err| {
+                        Error::Database(format!("Unable to store secret \"{}\"", &secret.path), err)
+                    })?;
+
+                history::record_secret(&mut crate::connect_db()?, &secret)?;
+
+                success!("Stored secret", &secret.path);
+                Ok(())
+            })
+        })
+}
+
+fn sync() -> seahorse::Command {
+    seahorse::Command::new("sync")
+        .description("Push local edits, pull missing or outdated secrets, and resolve conflicts")
+        .usage("nova secrets sync [--prefer local|remote]")
+        .flag(
+            seahorse::Flag::new("prefer", seahorse::FlagType::String)
+                .description("Resolve conflicts without prompting: local or remote"),
+        )
+        .action(|context| {
+            crate::run(context, |context| {
+                let prefer = match context.string_flag("prefer") {
+                    Ok(prefer) if prefer == "local" => Some('l'),
+                    Ok(prefer) if prefer == "remote" => Some('r'),
+                    Ok(prefer) => {
+                        return Err(Error::Usage(format!(
+                            "Unknown side to prefer \"{}\", expected local or remote",
+                            prefer
+                        )))
+                    }
+                    Err(_) => None,
+                };
+
+                let location =
+                    locate().ok_or_else(|| Error::NotFound("Invalid project path".into()))?;
+                let cipher = crypto::unlock(&mut crate::connect_db()?)?;
+
+                let conflict = |comparison: &Comparison, local: &str| -> Result<char> {
+                    match prefer {
+                        Some(side) => Ok(side),
+                        None if prompt::interactive() => {
+                            let path = &comparison.secret.path;
+                            print!(
+                                "{}",
+                                diff::unified(
+                                    &comparison.stored,
+                                    local,
+                                    &format!("{} (remote)", path),
+                                    &format!("{} (local)", path),
+                                )
+                            );
+                            prompt::choose(
+                                &format!("Conflicting secret \"{}\", keep", path),
+                                &["local", "remote", "skip"],
+                            )
+                        }
+                        None => Ok('s'),
+                    }
+                };
+
+                let mut unresolved = 0;
+                for comparison in compare(&location, &cipher)? {
+                    let path = &comparison.secret.path;
+                    let local = match &comparison.local {
+                        Some(local) => local,
+                        None => {
+                            pull(&comparison)?;
+                            success!("Pulled missing secret", path);
+                            continue;
+                        }
+                    };
+
+                    if *local == comparison.stored {
+                        mark_synced(&comparison.secret)?;
+                        continue;
+                    }
 
-            let cwd_relative_path = match context.args.first() {
-                Some(path) => path.to_string().replace('\\', "/"),
-                None => {
-                    error!("Please provide a path to the secret file");
-                    return;
+                    let synced_at = comparison.secret.synced_at;
+                    let local_changed = time::modified(&comparison.absolute_path)
+                        .is_none_or(|modified| modified > synced_at);
+                    let stored_changed = comparison.secret.updated_at > synced_at;
+
+                    // Without a recorded sync, neither side is known to be newer
+                    let side = match (local_changed, stored_changed) {
+                        _ if synced_at == 0 => conflict(&comparison, local)?,
+                        (true, false) => 'l',
+                        (false, true) => 'r',
+                        _ => conflict(&comparison, local)?,
+                    };
+
+                    match side {
+                        'l' => {
+                            push(&comparison, &cipher)?;
+                            success!("Pushed local secret", path);
+                        }
+                        'r' => {
+                            pull(&comparison)?;
+                            success!("Pulled remote secret", path);
+                        }
+                        _ => {
+                            warn!("Skipped conflicting secret", path);
+                            unresolved += 1;
+                        }
+                    }
                 }
-            };
 
-            let content = match std::fs::read_to_string(&cwd_relative_path) {
-                Ok(content) => content,
-                Err(err) => {
-                    error!("Unable to read from file", cwd_relative_path; err);
-                    return;
+                if unresolved > 0 {
+                    return Err(Error::Conflict(format!(
+                        "{} conflicting secret(s) left unresolved",
+                        unresolved
+                    )));
                 }
-            };
 
-            let secret = Secret {
-                project: location.project,
-                path: std::path::PathBuf::from(&location.folder.unwrap_or_default())
-                    .join(&cwd_relative_path)
-                    .to_str()
+                Ok(())
+            })
+        })
+}
+
+fn edit() -> seahorse::Command {
+    seahorse::Command::new("edit")
+        .description("Edit a repository secret in $VISUAL, $EDITOR or the configured editor")
+        .usage("nova secrets edit [path/to/config]")
+        .action(|context| {
+            crate::run(context, |context| {
+                let location =
+                    locate().ok_or_else(|| Error::NotFound("Invalid project path".into()))?;
+                let path = context.args.first().ok_or_else(|| {
+                    Error::Usage("Please provide a path to the secret file".into())
+                })?;
+
+                let connection = &mut crate::connect_db()?;
+                let cipher = crypto::unlock(connection)?;
+                let secret = find(&location, path)?;
+                let stored = cipher.decrypt(&secret.content)?;
+
+                let filename = std::path::Path::new(&secret.path)
+                    .file_name()
                     .unwrap()
-                    .into(),
-                content,
-            };
-
-            let upsert = match secrets::dsl::secrets
-                .filter(secrets::project.eq(&secret.project))
-                .filter(secrets::path.eq(&secret.path))
-                .first::<Secret>(&mut crate::connect_db())
-            {
-                Ok(_) => diesel::update(secrets::dsl::secrets)
+                    .to_str()
+                    .unwrap();
+                let content = editor::edit_secret(&stored, filename)?;
+
+                if content == stored {
+                    warn!("No changes made to secret", secret.path);
+                    return Ok(());
+                }
+
+                let secret = Secret {
+                    content: cipher.encrypt(&content),
+                    updated_at: time::now(),
+                    ..secret
+                };
+
+                diesel::update(secrets::dsl::secrets)
                     .filter(secrets::project.eq(&secret.project))
                     .filter(secrets::path.eq(&secret.path))
-                    .set(secrets::content.eq(&secret.content))
-                    .execute(&mut crate::connect_db()),
-                Err(_) => diesel::insert_into(secrets::dsl::secrets)
-                    .values(&secret)
-                    .execute(&mut crate::connect_db()),
-            };
+                    .set((
+                        secrets::content.eq(&secret.content),
+                        secrets::updated_at.eq(secret.updated_at),
+                    ))
+                    .execute(connection)
+                    .map_err(|err| {
+                        Error::Database(format!("Unable to store secret \"{}\"", secret.path), err)
+                    })?;
 
-            match upsert {
-                Ok(_) => {
-                    success!("Stored secret", &secret.path);
-                }
-                Err(err) => {
-                    error!("Unable to store secret", &secret.path; err);
-                }
-            }
+                history::record_secret(connection, &secret)?;
+
+                success!("Updated secret", secret.path);
+                Ok(())
+            })
+        })
+}
+
+fn log() -> seahorse::Command {
+    seahorse::Command::new("log")
+        .description("List the kept revisions of a repository secret")
+        .usage("nova secrets log [path/to/config]")
+        .action(|context| {
+            crate::run(context, |context| {
+                let location =
+                    locate().ok_or_else(|| Error::NotFound("Invalid project path".into()))?;
+                let path = context.args.first().ok_or_else(|| {
+                    Error::Usage("Please provide a path to the secret file".into())
+                })?;
+
+                let secret = find(&location, path)?;
+                let records = history::secret_log(&mut crate::connect_db()?, &secret)?
+                    .into_iter()
+                    .map(|version| Record {
+                        revision: version.revision,
+                        date: time::datetime(version.created_at),
+                        size: crypto::plaintext_len(&version.content),
+                        ..Default::default()
+                    })
+                    .collect::<Vec<_>>();
+
+                output::print_records(
+                    &[
+                        ("Revision", Field::Revision),
+                        ("Date", Field::Date),
+                        ("Content Length", Field::Size),
+                    ],
+                    &records,
+                )?;
+                Ok(())
+            })
+        })
+}
+
+fn show() -> seahorse::Command {
+    seahorse::Command::new("show")
+        .description("Print a repository secret, or one of its previous revisions")
+        .usage("nova secrets show [path/to/config] [--rev revision]")
+        .flag(revision_flag())
+        .action(|context| {
+            crate::run(context, |context| {
+                let location =
+                    locate().ok_or_else(|| Error::NotFound("Invalid project path".into()))?;
+                let path = context.args.first().ok_or_else(|| {
+                    Error::Usage("Please provide a path to the secret file".into())
+                })?;
+
+                let connection = &mut crate::connect_db()?;
+                let cipher = crypto::unlock(connection)?;
+                let secret = find(&location, path)?;
+
+                let content = match revision(context)? {
+                    Some(revision) => {
+                        history::secret_revision(connection, &secret, revision)?.content
+                    }
+                    None => secret.content,
+                };
+
+                print!("{}", cipher.decrypt(&content)?);
+                Ok(())
+            })
+        })
+}
+
+fn restore() -> seahorse::Command {
+    seahorse::Command::new("restore")
+        .description("Restore a repository secret to a previous revision")
+        .usage("nova secrets restore [path/to/config] --rev [revision]")
+        .flag(revision_flag())
+        .action(|context| {
+            crate::run(context, |context| {
+                let location =
+                    locate().ok_or_else(|| Error::NotFound("Invalid project path".into()))?;
+                let path = context.args.first().ok_or_else(|| {
+                    Error::Usage("Please provide a path to the secret file".into())
+                })?;
+                let revision = revision(context)?.ok_or_else(|| {
+                    Error::Usage("Please provide a revision to restore with --rev".into())
+                })?;
+
+                let connection = &mut crate::connect_db()?;
+                let cipher = crypto::unlock(connection)?;
+                let secret = find(&location, path)?;
+                let version = history::secret_revision(connection, &secret, revision)?;
+                cipher.decrypt(&version.content)?;
+
+                let secret = Secret {
+                    content: version.content,
+                    updated_at: time::now(),
+                    ..secret
+                };
+
+                diesel::update(secrets::dsl::secrets)
+                    .filter(secrets::project.eq(&secret.project))
+                    .filter(secrets::path.eq(&secret.path))
+                    .set((
+                        secrets::content.eq(&secret.content),
+                        secrets::updated_at.eq(secret.updated_at),
+                    ))
+                    .execute(connection)
+                    .map_err(|err| {
+                        Error::Database(format!("Unable to store secret \"{}\"", secret.path), err)
+                    })?;
+
+                history::record_secret(connection, &secret)?;
+
+                success!(format!(
+                    "Restored secret \"{}\" to revision {}, clone or sync to update the file",
+                    secret.path, revision
+                ));
+                Ok(())
+            })
         })
 }
 
 fn remove() -> seahorse::Command {
     seahorse::Command::new("remove")
-        .description("Remove a repository secret")
-        .usage("nova secrts remove [path/to/config]")
+        .description("Remove a repository secret and its history")
+        .usage("nova secrets remove [path/to/config]")
         .action(|context| {
-            let location = match locate() {
-                Some(location) => location,
-                None => {
-                    error!("Invalid project path");
-                    return;
-                }
-            };
-
-            let cwd_relative_path = match context.args.first() {
-                Some(path) => path.to_string().replace('\\', "/"),
-                None => {
-                    error!("Please provide a path to the secret file");
-                    return;
-                }
-            };
+            crate::run(context, |context| {
+                let location =
+                    locate().ok_or_else(|| Error::NotFound("Invalid project path".into()))?;
 
-            let project_relative_path: String =
-                std::path::PathBuf::from(&location.folder.unwrap_or_default())
-                    .join(cwd_relative_path)
-                    .to_str()
-                    .unwrap()
-                    .into();
-
-            match diesel::delete(secrets::dsl::secrets)
-                .filter(secrets::project.eq(&location.project))
-                .filter(secrets::path.eq(&project_relative_path))
-                .execute(&mut crate::connect_db())
-            {
-                Ok(deleted) => {
-                    if deleted == 0 {
-                        error!("No secret found", project_relative_path);
-                    } else {
-                        success!("Removed secret", project_relative_path);
+                let cwd_relative_path = match context.args.first() {
+                    Some(path) => path.to_string().replace('\\', "/"),
+                    None => {
+                        return Err(Error::Usage(
+                            "Please provide a path to the secret file".into(),
+                        ))
                     }
+                };
+
+                let project_relative_path = project_relative_path(&location, &cwd_relative_path);
+
+                let connection = &mut crate::connect_db()?;
+                let projects = secrets::dsl::secrets
+                    .filter(owned_by(&location))
+                    .filter(secrets::path.eq(&project_relative_path))
+                    .select(secrets::project)
+                    .load::<String>(connection)
+                    .map_err(|err| Error::Database("Unable to fetch secrets".into(), err))?;
+
+                let deleted = diesel::delete(secrets::dsl::secrets)
+                    .filter(owned_by(&location))
+                    .filter(secrets::path.eq(&project_relative_path))
+                    .execute(connection)
+                    .map_err(|err| {
+                        Error::Database(
+                            format!("Unable to remove secret \"{}\"", project_relative_path),
+                            err,
+                        )
+                    })?;
+
+                for project in projects {
+                    history::forget_secret(connection, &project, &project_relative_path)?;
                 }
-                Err(err) => {
-                    error!("Unable to remove secret", project_relative_path; err);
+
+                if deleted == 0 {
+                    return Err(Error::NotFound(format!(
+                        "No secret found \"{}\"",
+                        project_relative_path
+                    )));
                 }
-            };
+
+                success!("Removed secret", project_relative_path);
+                Ok(())
+            })
         })
 }
 
@@ -266,6 +693,11 @@ pub fn secrets() -> seahorse::Command {
         .command(clone())
         .command(check())
         .command(set())
+        .command(sync())
+        .command(edit())
+        .command(log())
+        .command(show())
+        .command(restore())
         .command(remove())
-        .action(|context| context.help())
+        .action(crate::help)
 }
@@ -0,0 +1,204 @@
+use {
+    crate::{
+        error::{Error, Result},
+        models::Vault,
+        schema::{secret_versions, secrets, vault},
+    },
+    base64::Engine,
+    chacha20poly1305::{
+        aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+        XChaCha20Poly1305, XNonce,
+    },
+    diesel::prelude::*,
+};
+
+static PREFIX: &str = "nova:v1:";
+static VERIFIER: &str = "nova";
+static NONCE_LENGTH: usize = 24;
+static TAG_LENGTH: usize = 16;
+
+/// Encrypts and decrypts secret contents with a key derived from the master passphrase
+pub struct Cipher {
+    cipher: XChaCha20Poly1305,
+}
+
+impl Cipher {
+    fn derive(passphrase: &str, salt: &[u8]) -> Result<Cipher> {
+        let mut key = [0u8; 32];
+        argon2::Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+            .map_err(|err| {
+                Error::Crypto(format!("Unable to derive key from passphrase\n{}", err))
+            })?;
+
+        Ok(Cipher {
+            cipher: XChaCha20Poly1305::new(&key.into()),
+        })
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> String {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .expect("Encryption failed");
+
+        let mut bytes = nonce.to_vec();
+        bytes.extend(ciphertext);
+        format!(
+            "{}{}",
+            PREFIX,
+            base64::engine::general_purpose::STANDARD.encode(bytes)
+        )
+    }
+
+    pub fn decrypt(&self, content: &str) -> Result<String> {
+        let bytes = decode(content)
+            .ok_or_else(|| Error::Crypto("Secret is not encrypted or is corrupted".into()))?;
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LENGTH);
+
+        let plaintext = self
+            .cipher
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map_err(|_| {
+                Error::Crypto("Unable to decrypt secret, wrong passphrase or tampered data".into())
+            })?;
+
+        String::from_utf8(plaintext)
+            .map_err(|_| Error::Crypto("Decrypted secret is not valid UTF-8".into()))
+    }
+}
+
+fn decode(content: &str) -> Option<Vec<u8>> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(content.strip_prefix(PREFIX)?)
+        .ok()?;
+
+    if bytes.len() < NONCE_LENGTH + TAG_LENGTH {
+        return None;
+    }
+
+    Some(bytes)
+}
+
+/// Length of the decrypted content, computed without needing the passphrase
+pub fn plaintext_len(content: &str) -> usize {
+    match decode(content) {
+        Some(bytes) => bytes.len() - NONCE_LENGTH - TAG_LENGTH,
+        None => content.len(),
+    }
+}
+
+fn read_passphrase(prompt: &str) -> Result<String> {
+    if let Ok(passphrase) = std::env::var("NOVA_PASSPHRASE") {
+        return Ok(passphrase);
+    }
+
+    rpassword::prompt_password(prompt)
+        .map_err(|err| Error::Io("Unable to read passphrase".into(), err))
+}
+
+fn create(connection: &mut SqliteConnection) -> Result<Cipher> {
+    let passphrase = read_passphrase("New master passphrase: ")?;
+    if std::env::var("NOVA_PASSPHRASE").is_err()
+        && read_passphrase("Confirm master passphrase: ")? != passphrase
+    {
+        return Err(Error::Crypto("Passphrases do not match".into()));
+    }
+    if passphrase.is_empty() {
+        return Err(Error::Usage("Passphrase cannot be empty".into()));
+    }
+
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    let cipher = Cipher::derive(&passphrase, &salt)?;
+
+    diesel::insert_into(vault::dsl::vault)
+        .values(&Vault {
+            id: 0,
+            salt: base64::engine::general_purpose::STANDARD.encode(salt),
+            verifier: cipher.encrypt(VERIFIER),
+        })
+        .execute(connection)
+        .map_err(|err| Error::Database("Unable to store master key".into(), err))?;
+
+    Ok(cipher)
+}
+
+/// Encrypts rows stored before secrets were encrypted at rest, including the revisions copied
+/// from them into the history
+fn encrypt_plaintext_rows(connection: &mut SqliteConnection, cipher: &Cipher) -> Result<()> {
+    let rows = secrets::dsl::secrets
+        .filter(secrets::content.not_like(format!("{}%", PREFIX)))
+        .select((secrets::project, secrets::path, secrets::content))
+        .load::<(String, String, String)>(connection)
+        .map_err(|err| Error::Database("Unable to fetch secrets".into(), err))?;
+    let versions = secret_versions::dsl::secret_versions
+        .filter(secret_versions::content.not_like(format!("{}%", PREFIX)))
+        .select((
+            secret_versions::project,
+            secret_versions::path,
+            secret_versions::revision,
+            secret_versions::content,
+        ))
+        .load::<(String, String, i32, String)>(connection)
+        .map_err(|err| Error::Database("Unable to fetch secret history".into(), err))?;
+
+    if rows.is_empty() && versions.is_empty() {
+        return Ok(());
+    }
+
+    connection
+        .transaction::<_, diesel::result::Error, _>(|connection| {
+            for (project, path, content) in &rows {
+                diesel::update(secrets::dsl::secrets)
+                    .filter(secrets::project.eq(project))
+                    .filter(secrets::path.eq(path))
+                    .set(secrets::content.eq(cipher.encrypt(content)))
+                    .execute(connection)?;
+            }
+            for (project, path, revision, content) in &versions {
+                diesel::update(secret_versions::dsl::secret_versions)
+                    .filter(secret_versions::project.eq(project))
+                    .filter(secret_versions::path.eq(path))
+                    .filter(secret_versions::revision.eq(revision))
+                    .set(secret_versions::content.eq(cipher.encrypt(content)))
+                    .execute(connection)?;
+            }
+            Ok(())
+        })
+        .map_err(|err| Error::Database("Unable to encrypt existing secrets".into(), err))?;
+
+    // On stderr so it never ends up in output that is piped or parsed, like `secrets show`
+    if !rows.is_empty() {
+        eprintln!("[SUCCESS] Encrypted {} existing secret(s)", rows.len());
+    }
+    Ok(())
+}
+
+/// Derives the secrets key from the master passphrase, creating one on first use
+pub fn unlock(connection: &mut SqliteConnection) -> Result<Cipher> {
+    let vault = vault::dsl::vault
+        .first::<Vault>(connection)
+        .optional()
+        .map_err(|err| Error::Database("Unable to fetch master key".into(), err))?;
+
+    let cipher = match vault {
+        Some(vault) => {
+            let salt = base64::engine::general_purpose::STANDARD
+                .decode(&vault.salt)
+                .map_err(|_| Error::Crypto("Stored master key salt is corrupted".into()))?;
+            let cipher = Cipher::derive(&read_passphrase("Master passphrase: ")?, &salt)?;
+
+            if cipher.decrypt(&vault.verifier).ok().as_deref() != Some(VERIFIER) {
+                return Err(Error::Crypto("Incorrect master passphrase".into()));
+            }
+
+            cipher
+        }
+        None => create(connection)?,
+    };
+
+    encrypt_plaintext_rows(connection, &cipher)?;
+    Ok(cipher)
+}
@@ -0,0 +1,42 @@
+use std::io::IsTerminal;
+
+static RED: &str = "\x1b[31m";
+static GREEN: &str = "\x1b[32m";
+static CYAN: &str = "\x1b[36m";
+static BOLD: &str = "\x1b[1m";
+static RESET: &str = "\x1b[0m";
+
+fn colored() -> bool {
+    std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal()
+}
+
+/// Renders a unified diff from `old` to `new`, colored when printing to a terminal
+pub fn unified(old: &str, new: &str, old_header: &str, new_header: &str) -> String {
+    let diff = similar::TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(3)
+        .header(old_header, new_header)
+        .to_string();
+
+    if !colored() {
+        return diff;
+    }
+
+    diff.lines()
+        .map(|line| {
+            let color = if line.starts_with("---") || line.starts_with("+++") {
+                BOLD
+            } else if line.starts_with("@@") {
+                CYAN
+            } else if line.starts_with('-') {
+                RED
+            } else if line.starts_with('+') {
+                GREEN
+            } else {
+                return format!("{}\n", line);
+            };
+
+            format!("{}{}{}\n", color, line, RESET)
+        })
+        .collect()
+}
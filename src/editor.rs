@@ -0,0 +1,119 @@
+use {
+    crate::{
+        error::{Error, Result},
+        settings,
+    },
+    std::os::unix::fs::OpenOptionsExt,
+};
+
+/// The editor from `$VISUAL`, then `$EDITOR`, then the `editor` setting
+fn editor() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|variable| std::env::var(variable).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| settings::get().editor.clone())
+}
+
+/// Opens the content in the editor and returns what was saved. The temp file is named
+/// `filename` inside a private temp folder so the editor can detect its syntax
+pub fn edit(content: &str, filename: &str) -> Result<String> {
+    let folder = tempfile::Builder::new()
+        .prefix("nova-")
+        .tempdir()
+        .map_err(|err| Error::Io("Unable to create temp folder".into(), err))?;
+
+    open(folder.path(), content, filename)
+}
+
+/// Overwrites every file in the folder with zeros before the folder is removed
+struct Shredded(tempfile::TempDir);
+
+impl Drop for Shredded {
+    fn drop(&mut self) {
+        for entry in std::fs::read_dir(self.0.path())
+            .into_iter()
+            .flatten()
+            .flatten()
+        {
+            let length = entry
+                .metadata()
+                .map(|metadata| metadata.len())
+                .unwrap_or_default();
+            if let Ok(mut file) = std::fs::OpenOptions::new().write(true).open(entry.path()) {
+                let _ = std::io::Write::write_all(&mut file, &vec![0; length as usize]);
+                let _ = file.sync_all();
+            }
+        }
+    }
+}
+
+/// A memory backed folder, so decrypted secrets are never written to disk
+fn tmpfs() -> Result<std::path::PathBuf> {
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .map(std::path::PathBuf::from)
+        .into_iter()
+        .chain([std::path::PathBuf::from("/dev/shm")])
+        .find(|folder| folder.is_dir())
+        .ok_or_else(|| {
+            Error::Io(
+                "Unable to find a memory backed folder for the decrypted secret".into(),
+                std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "Neither $XDG_RUNTIME_DIR nor /dev/shm exist",
+                ),
+            )
+        })
+}
+
+/// Like [`edit`], but the temp file is kept in memory and shredded once the editor exits
+pub fn edit_secret(content: &str, filename: &str) -> Result<String> {
+    let folder = tempfile::Builder::new()
+        .prefix("nova-")
+        .tempdir_in(tmpfs()?)
+        .map(Shredded)
+        .map_err(|err| Error::Io("Unable to create temp folder".into(), err))?;
+
+    open(folder.0.path(), content, filename)
+}
+
+fn open(folder: &std::path::Path, content: &str, filename: &str) -> Result<String> {
+    let path = folder.join(filename);
+
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&path)
+        .and_then(|mut file| std::io::Write::write_all(&mut file, content.as_bytes()))
+        .map_err(|err| {
+            Error::Io(
+                format!("Unable to write to temp file \"{}\"", filename),
+                err,
+            )
+        })?;
+
+    // Run through the shell like git does, so editors with arguments like "code --wait" work
+    let editor = editor();
+    let status = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$@\"", editor))
+        .arg(&editor)
+        .arg(&path)
+        .status()
+        .map_err(|err| Error::Process(format!("Unable to run editor \"{}\"\n{}", editor, err)))?;
+
+    if !status.success() {
+        return Err(Error::Process(format!(
+            "Editor \"{}\" exited with {}, discarding changes",
+            editor, status
+        )));
+    }
+
+    std::fs::read_to_string(&path).map_err(|err| {
+        Error::Io(
+            format!("Unable to read from temp file \"{}\"", filename),
+            err,
+        )
+    })
+}
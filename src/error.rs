@@ -0,0 +1,56 @@
+/// Every way a command can fail, each mapped to its own exit code
+#[derive(Debug)]
+pub enum Error {
+    /// Missing or invalid command line arguments
+    Usage(String),
+    /// A config, secret or project that does not exist
+    NotFound(String),
+    /// Something that already exists or disagrees with what is stored
+    Conflict(String),
+    /// File contents that could not be parsed
+    Parse(String),
+    Io(String, std::io::Error),
+    Database(String, diesel::result::Error),
+    /// Secrets that could not be unlocked or decrypted
+    Crypto(String),
+    /// External programs that failed to run or exited with an error
+    Process(String),
+    /// An invalid settings file or database location
+    Settings(String),
+}
+
+impl Error {
+    pub fn code(&self) -> i32 {
+        match self {
+            Error::Usage(_) => 2,
+            Error::NotFound(_) => 3,
+            Error::Conflict(_) => 4,
+            Error::Parse(_) => 5,
+            Error::Io(..) => 6,
+            Error::Database(..) => 7,
+            Error::Crypto(_) => 8,
+            Error::Process(_) => 9,
+            Error::Settings(_) => 10,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Error::Usage(message)
+            | Error::NotFound(message)
+            | Error::Conflict(message)
+            | Error::Parse(message)
+            | Error::Crypto(message)
+            | Error::Process(message)
+            | Error::Settings(message) => write!(f, "{}", message),
+            Error::Io(message, err) => write!(f, "{}\n{}", message, err),
+            Error::Database(message, err) => write!(f, "{}\n{}", message, err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub type Result<T> = std::result::Result<T, Error>;
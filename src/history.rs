@@ -0,0 +1,215 @@
+use {
+    crate::{
+        error::{Error, Result},
+        models::{Config, ConfigVersion, Secret, SecretVersion},
+        schema::{config_versions, configs, secret_versions},
+        settings, time,
+    },
+    diesel::{dsl::max, prelude::*},
+};
+
+/// Path of the row recorded for a revision in which a config has no files, like after it was
+/// removed, so the revision still shows up in its log
+static NO_FILES: &str = "";
+
+/// Records the current files of a config shorthand as its next revision
+pub fn record_config(
+    connection: &mut SqliteConnection,
+    shorthand: &str,
+    message: &str,
+) -> Result<i32> {
+    let latest = config_versions::dsl::config_versions
+        .filter(config_versions::shorthand.eq(shorthand))
+        .select(max(config_versions::revision))
+        .first::<Option<i32>>(connection)
+        .map_err(|err| Error::Database("Unable to fetch config history".into(), err))?;
+    let revision = latest.unwrap_or_default() + 1;
+
+    let created_at = time::now();
+    let mut versions = configs::dsl::configs
+        .filter(configs::shorthand.eq(shorthand))
+        .load::<Config>(connection)
+        .map_err(|err| Error::Database("Unable to fetch configs".into(), err))?
+        .into_iter()
+        .map(|config| ConfigVersion {
+            shorthand: config.shorthand,
+            revision,
+            path: config.path,
+            content: config.content,
+            message: message.to_string(),
+            created_at,
+        })
+        .collect::<Vec<_>>();
+
+    if versions.is_empty() {
+        versions.push(ConfigVersion {
+            shorthand: shorthand.to_string(),
+            revision,
+            path: NO_FILES.to_string(),
+            content: String::new(),
+            message: message.to_string(),
+            created_at,
+        });
+    }
+
+    diesel::insert_into(config_versions::dsl::config_versions)
+        .values(&versions)
+        .execute(connection)
+        .map_err(|err| {
+            Error::Database(
+                format!("Unable to record history of config \"{}\"", shorthand),
+                err,
+            )
+        })?;
+
+    Ok(revision)
+}
+
+/// Every revision of a config shorthand, newest first
+pub fn config_log(
+    connection: &mut SqliteConnection,
+    shorthand: &str,
+) -> Result<Vec<ConfigVersion>> {
+    let mut versions = config_versions::dsl::config_versions
+        .filter(config_versions::shorthand.eq(shorthand))
+        .order(config_versions::revision.desc())
+        .load::<ConfigVersion>(connection)
+        .map_err(|err| Error::Database("Unable to fetch config history".into(), err))?;
+
+    versions.dedup_by_key(|version| version.revision);
+    Ok(versions)
+}
+
+/// Every revision of one file of a config shorthand, newest first
+pub fn config_file_log(
+    connection: &mut SqliteConnection,
+    shorthand: &str,
+    path: &str,
+) -> Result<Vec<ConfigVersion>> {
+    config_versions::dsl::config_versions
+        .filter(config_versions::shorthand.eq(shorthand))
+        .filter(config_versions::path.eq(path))
+        .order(config_versions::revision.desc())
+        .load::<ConfigVersion>(connection)
+        .map_err(|err| Error::Database("Unable to fetch config history".into(), err))
+}
+
+/// The files of a config shorthand as they were at a revision, which may be none if it was
+/// removed at that revision
+pub fn config_revision(
+    connection: &mut SqliteConnection,
+    shorthand: &str,
+    revision: i32,
+) -> Result<Vec<ConfigVersion>> {
+    let mut versions = config_versions::dsl::config_versions
+        .filter(config_versions::shorthand.eq(shorthand))
+        .filter(config_versions::revision.eq(revision))
+        .order(config_versions::path)
+        .load::<ConfigVersion>(connection)
+        .map_err(|err| Error::Database("Unable to fetch config history".into(), err))?;
+
+    if versions.is_empty() {
+        return Err(Error::NotFound(format!(
+            "Config \"{}\" has no revision {}",
+            shorthand, revision
+        )));
+    }
+
+    versions.retain(|version| version.path != NO_FILES);
+    Ok(versions)
+}
+
+/// Records the content of a secret as its next revision, then drops revisions past the
+/// retention limit of its project
+pub fn record_secret(connection: &mut SqliteConnection, secret: &Secret) -> Result<()> {
+    let latest = secret_versions::dsl::secret_versions
+        .filter(secret_versions::project.eq(&secret.project))
+        .filter(secret_versions::path.eq(&secret.path))
+        .select(max(secret_versions::revision))
+        .first::<Option<i32>>(connection)
+        .map_err(|err| Error::Database("Unable to fetch secret history".into(), err))?;
+    let revision = latest.unwrap_or_default() + 1;
+
+    diesel::insert_into(secret_versions::dsl::secret_versions)
+        .values(&SecretVersion {
+            project: secret.project.clone(),
+            path: secret.path.clone(),
+            revision,
+            content: secret.content.clone(),
+            created_at: time::now(),
+        })
+        .execute(connection)
+        .map_err(|err| {
+            Error::Database(
+                format!("Unable to record history of secret \"{}\"", secret.path),
+                err,
+            )
+        })?;
+
+    let kept = settings::get()
+        .secrets
+        .history(&secret.project, secret.remote.as_deref()) as i32;
+    diesel::delete(secret_versions::dsl::secret_versions)
+        .filter(secret_versions::project.eq(&secret.project))
+        .filter(secret_versions::path.eq(&secret.path))
+        .filter(secret_versions::revision.le(revision - kept))
+        .execute(connection)
+        .map_err(|err| {
+            Error::Database(
+                format!("Unable to prune history of secret \"{}\"", secret.path),
+                err,
+            )
+        })?;
+
+    Ok(())
+}
+
+/// Every kept revision of a secret, newest first
+pub fn secret_log(
+    connection: &mut SqliteConnection,
+    secret: &Secret,
+) -> Result<Vec<SecretVersion>> {
+    secret_versions::dsl::secret_versions
+        .filter(secret_versions::project.eq(&secret.project))
+        .filter(secret_versions::path.eq(&secret.path))
+        .order(secret_versions::revision.desc())
+        .load::<SecretVersion>(connection)
+        .map_err(|err| Error::Database("Unable to fetch secret history".into(), err))
+}
+
+/// A secret as it was at a revision
+pub fn secret_revision(
+    connection: &mut SqliteConnection,
+    secret: &Secret,
+    revision: i32,
+) -> Result<SecretVersion> {
+    secret_versions::dsl::secret_versions
+        .filter(secret_versions::project.eq(&secret.project))
+        .filter(secret_versions::path.eq(&secret.path))
+        .filter(secret_versions::revision.eq(revision))
+        .first::<SecretVersion>(connection)
+        .optional()
+        .map_err(|err| Error::Database("Unable to fetch secret history".into(), err))?
+        .ok_or_else(|| {
+            Error::NotFound(format!(
+                "Secret \"{}\" has no revision {}",
+                secret.path, revision
+            ))
+        })
+}
+
+/// Forgets every revision of a secret
+pub fn forget_secret(connection: &mut SqliteConnection, project: &str, path: &str) -> Result<()> {
+    diesel::delete(secret_versions::dsl::secret_versions)
+        .filter(secret_versions::project.eq(project))
+        .filter(secret_versions::path.eq(path))
+        .execute(connection)
+        .map_err(|err| {
+            Error::Database(
+                format!("Unable to remove history of secret \"{}\"", path),
+                err,
+            )
+        })?;
+
+    Ok(())
+}
@@ -1,25 +1,131 @@
 mod commands;
+mod crypto;
+mod diff;
+mod editor;
+mod error;
+mod history;
+mod manifest;
+mod merge;
 mod models;
 mod output;
+mod overwrite;
+mod project;
+mod prompt;
 mod schema;
+mod settings;
+mod template;
+mod time;
 
-pub fn connect_db() -> diesel::SqliteConnection {
-    if sudo::escalate_if_needed().is_err() {
-        panic!("Sudo permission required to access secrets");
+use error::Error;
+
+const MIGRATIONS: diesel_migrations::EmbeddedMigrations = diesel_migrations::embed_migrations!();
+static MIGRATED: std::sync::Once = std::sync::Once::new();
+
+pub fn connect_db() -> error::Result<diesel::SqliteConnection> {
+    let path = &settings::get().database;
+    if let Some(parent) = path.parent() {
+        std::os::unix::fs::DirBuilderExt::mode(std::fs::DirBuilder::new().recursive(true), 0o700)
+            .create(parent)
+            .map_err(|err| {
+                Error::Io(
+                    format!("Unable to create database folder \"{}\"", parent.display()),
+                    err,
+                )
+            })?;
     }
 
-    <diesel::SqliteConnection as diesel::Connection>::establish("file:/Users/mac/nova.db")
-        .unwrap_or_else(|_| panic!("Error connecting to /Users/mac/nova.db"))
+    std::os::unix::fs::OpenOptionsExt::mode(
+        std::fs::OpenOptions::new().create(true).append(true),
+        0o600,
+    )
+    .open(path)
+    .and_then(|file| file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600)))
+    .map_err(|err| {
+        Error::Io(
+            format!("Unable to secure database \"{}\"", path.display()),
+            err,
+        )
+    })?;
+
+    let mut connection = <diesel::SqliteConnection as diesel::Connection>::establish(&format!(
+        "file:{}",
+        path.display()
+    ))
+    .map_err(|err| {
+        Error::Settings(format!(
+            "Unable to connect to database \"{}\"\n{}",
+            path.display(),
+            err
+        ))
+    })?;
+
+    let mut migrated = Ok(());
+    MIGRATED.call_once(|| {
+        migrated = diesel_migrations::MigrationHarness::run_pending_migrations(
+            &mut connection,
+            MIGRATIONS,
+        )
+        .map(|_| ())
+        .map_err(|err| {
+            Error::Settings(format!(
+                "Unable to migrate database \"{}\"\n{}",
+                path.display(),
+                err
+            ))
+        });
+    });
+    migrated?;
+
+    Ok(connection)
+}
+
+/// Runs a command action, exiting with the error code of the error it fails with
+pub fn run(context: &seahorse::Context, action: fn(&seahorse::Context) -> error::Result<()>) {
+    if let Err(err) = action(context) {
+        eprintln!("[ERROR] {}", err);
+        std::process::exit(err.code());
+    }
+}
+
+/// Shows the help of a command that only groups subcommands, failing on an unknown subcommand
+/// so that typos in scripts are not mistaken for success
+pub fn help(context: &seahorse::Context) {
+    run(context, |context| match context.args.first() {
+        Some(command) => Err(Error::Usage(format!(
+            "Unknown command \"{}\", see --help for the available commands",
+            command
+        ))),
+        None => {
+            context.help();
+            Ok(())
+        }
+    })
 }
 
 fn main() {
+    let args = match settings::init(std::env::args().collect::<Vec<String>>()) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("[ERROR] {}", err);
+            std::process::exit(err.code());
+        }
+    };
+
     let app = seahorse::App::new("nova")
         .description("A CLI for helping me with various tasks")
+        .flag(
+            seahorse::Flag::new("db", seahorse::FlagType::String)
+                .description("Path to the database, overrides $NOVA_DB and the settings file"),
+        )
+        .flag(
+            seahorse::Flag::new("format", seahorse::FlagType::String)
+                .description("Output format of list and check commands: table, json or plain"),
+        )
         .command(commands::configs())
         .command(commands::generate())
         .command(commands::secrets())
         .command(commands::setup())
-        .action(|config| config.help());
+        .action(help);
 
-    app.run(std::env::args().collect::<Vec<String>>());
+    app.run(args);
 }
@@ -0,0 +1,92 @@
+use {
+    super::{Dependency, Ecosystem, Kind, ManifestParser},
+    crate::error::{Error, Result},
+};
+
+pub struct CargoToml;
+
+impl ManifestParser for CargoToml {
+    fn filename(&self) -> &'static str {
+        "Cargo.toml"
+    }
+
+    fn parse(&self, text: &str) -> Result<Vec<Dependency>> {
+        let cargo = text
+            .parse::<toml::Value>()
+            .map_err(|err| Error::Parse(format!("Unable to parse Cargo.toml\n{}", err)))?;
+
+        let mut dependencies = vec![];
+        let section = match cargo
+            .get("dependencies")
+            .and_then(|section| section.as_table())
+        {
+            Some(section) => section,
+            None => return Ok(dependencies),
+        };
+
+        // Path and git dependencies may not have a version
+        for (name, version) in section {
+            let version = match version {
+                toml::Value::String(version) => Some(version.as_str()),
+                toml::Value::Table(table) => {
+                    table.get("version").and_then(|version| version.as_str())
+                }
+                _ => None,
+            };
+
+            if let Some(version) = version {
+                dependencies.push(Dependency::new(
+                    name,
+                    version,
+                    Ecosystem::Crates,
+                    Kind::Normal,
+                ));
+            }
+        }
+
+        Ok(dependencies)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_dependencies_in_file_order() {
+        let dependencies = CargoToml
+            .parse(
+                r#"
+                [dependencies]
+                serde = "1"
+                anyhow = { version = "1.0", features = ["backtrace"] }
+                local = { path = "../local" }
+
+                [dev-dependencies]
+                tempfile = "3"
+                "#,
+            )
+            .unwrap();
+
+        assert_eq!(
+            dependencies,
+            vec![
+                Dependency::new("serde", "1", Ecosystem::Crates, Kind::Normal),
+                Dependency::new("anyhow", "1.0", Ecosystem::Crates, Kind::Normal),
+            ]
+        );
+    }
+
+    #[test]
+    fn allows_missing_dependencies() {
+        assert!(CargoToml
+            .parse("[package]\nname = \"x\"")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn fails_on_invalid_toml() {
+        assert!(CargoToml.parse("[dependencies").is_err());
+    }
+}
@@ -0,0 +1,67 @@
+use {
+    super::{sort, Dependency, Ecosystem, Kind, ManifestParser},
+    crate::error::{Error, Result},
+};
+
+pub struct PubspecYaml;
+
+impl ManifestParser for PubspecYaml {
+    fn filename(&self) -> &'static str {
+        "pubspec.yaml"
+    }
+
+    fn parse(&self, text: &str) -> Result<Vec<Dependency>> {
+        let yaml = serde_yaml::from_str::<serde_yaml::Value>(text)
+            .map_err(|err| Error::Parse(format!("Unable to parse pubspec.yaml\n{}", err)))?;
+
+        let mut dependencies = vec![];
+        for (section, kind) in [
+            ("dependencies", Kind::Normal),
+            ("dev_dependencies", Kind::Dev),
+        ] {
+            let section = match yaml.get(section).and_then(|section| section.as_mapping()) {
+                Some(section) => section,
+                None => continue,
+            };
+
+            // SDK, path and git dependencies are maps without a version
+            for (name, version) in section {
+                if let (Some(name), Some(version)) = (name.as_str(), version.as_str()) {
+                    dependencies.push(Dependency::new(name, version, Ecosystem::Pub, kind));
+                }
+            }
+        }
+
+        sort(&mut dependencies);
+        Ok(dependencies)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_versioned_dependencies() {
+        let dependencies = PubspecYaml
+            .parse(
+                "dependencies:\n  http: ^1.1.0\n  flutter:\n    sdk: flutter\n  args: 2.0.0\n\
+                 dev_dependencies:\n  lints: ^2.0.0\n",
+            )
+            .unwrap();
+
+        assert_eq!(
+            dependencies,
+            vec![
+                Dependency::new("args", "2.0.0", Ecosystem::Pub, Kind::Normal),
+                Dependency::new("http", "^1.1.0", Ecosystem::Pub, Kind::Normal),
+                Dependency::new("lints", "^2.0.0", Ecosystem::Pub, Kind::Dev),
+            ]
+        );
+    }
+
+    #[test]
+    fn fails_on_invalid_yaml() {
+        assert!(PubspecYaml.parse("dependencies: [").is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use {
+    super::{Dependency, Ecosystem, Kind, ManifestParser},
+    crate::{error, error::Result},
+};
+
+pub struct BuildGradle;
+
+impl ManifestParser for BuildGradle {
+    fn filename(&self) -> &'static str {
+        "build.gradle"
+    }
+
+    fn parse(&self, text: &str) -> Result<Vec<Dependency>> {
+        let regex = regex::Regex::new(
+            r#"^(\w+) (?:['"](.+):(.+):(.+)['"]|\w+\(['"](.+):(.+):(.+)['"]\))$"#,
+        )
+        .unwrap();
+
+        let mut dependencies = vec![];
+        let mut reading_dependencies = false;
+        for line in text.split('\n') {
+            let line = line.trim();
+
+            if !reading_dependencies {
+                reading_dependencies = line == "dependencies {";
+                continue;
+            }
+
+            if line == "}" {
+                break;
+            }
+
+            if let Some(captures) = regex.captures(line) {
+                let capture = |index: usize| {
+                    captures
+                        .get(index)
+                        .unwrap_or_else(|| captures.get(index + 3).unwrap())
+                        .as_str()
+                };
+
+                let kind = match captures[1].starts_with("test") {
+                    true => Kind::Dev,
+                    false => Kind::Normal,
+                };
+
+                dependencies.push(Dependency::new(
+                    &format!("{}:{}", capture(2), capture(3)),
+                    capture(4),
+                    Ecosystem::Maven,
+                    kind,
+                ));
+            } else if !line.is_empty() && !line.starts_with("//") {
+                error!("Failed to parse dependency", line);
+            }
+        }
+
+        Ok(dependencies)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_dependencies_block_in_file_order() {
+        let dependencies = BuildGradle
+            .parse(
+                r#"
+                plugins {
+                    id 'java'
+                }
+
+                dependencies {
+                    implementation 'com.google.code.gson:gson:2.10'
+                    // A comment
+                    testImplementation "junit:junit:4.13.2"
+                    implementation platform('org.springframework.boot:spring-boot-dependencies:3.2.0')
+                }
+
+                repositories {
+                    implementation 'not:a:dependency'
+                }
+                "#,
+            )
+            .unwrap();
+
+        assert_eq!(
+            dependencies,
+            vec![
+                Dependency::new(
+                    "com.google.code.gson:gson",
+                    "2.10",
+                    Ecosystem::Maven,
+                    Kind::Normal
+                ),
+                Dependency::new("junit:junit", "4.13.2", Ecosystem::Maven, Kind::Dev),
+                Dependency::new(
+                    "org.springframework.boot:spring-boot-dependencies",
+                    "3.2.0",
+                    Ecosystem::Maven,
+                    Kind::Normal
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_lines_it_cannot_parse() {
+        let dependencies = BuildGradle
+            .parse("dependencies {\n    implementation files('libs/a.jar')\n}\n")
+            .unwrap();
+
+        assert!(dependencies.is_empty());
+    }
+}
@@ -0,0 +1,208 @@
+//! Parsers that read the dependencies of a project manifest, and the renderer that turns them
+//! into the badges of the "Built with" section of a README
+
+mod cargo;
+mod dart;
+mod gradle;
+mod npm;
+mod python;
+
+use crate::error::Result;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Ecosystem {
+    Npm,
+    Pub,
+    Crates,
+    Maven,
+    PyPI,
+}
+
+impl Ecosystem {
+    fn color(self) -> &'static str {
+        match self {
+            Ecosystem::Npm => "red",
+            Ecosystem::Pub => "blue",
+            Ecosystem::Crates => "yellow",
+            Ecosystem::Maven => "brightgreen",
+            Ecosystem::PyPI => "blueviolet",
+        }
+    }
+
+    /// Link to the page of a version of the package in the registry
+    fn url(self, name: &str, version: &str) -> String {
+        let version = version.replace(['^', '~'], "");
+        match self {
+            Ecosystem::Npm => format!("https://npmjs.com/package/{}/v/{}", name, version),
+            Ecosystem::Pub => format!("https://pub.dev/packages/{}/versions/{}", name, version),
+            Ecosystem::Crates => format!("https://crates.io/crates/{}/{}", name, version),
+            Ecosystem::Maven => format!(
+                "https://mvnrepository.com/artifact/{}/{}",
+                name.replace(':', "/"),
+                version
+            ),
+            Ecosystem::PyPI => format!(
+                "https://pypi.org/project/{}/{}",
+                name,
+                python::version(&version).unwrap_or_default()
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Kind {
+    Normal,
+    Dev,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Dependency {
+    pub name: String,
+    /// The version requirement as written in the manifest
+    pub version: String,
+    pub ecosystem: Ecosystem,
+    pub kind: Kind,
+}
+
+impl Dependency {
+    fn new(name: &str, version: &str, ecosystem: Ecosystem, kind: Kind) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            version: version.to_string(),
+            ecosystem,
+            kind,
+        }
+    }
+}
+
+pub trait ManifestParser: Sync {
+    /// File name of the manifest, like `Cargo.toml`
+    fn filename(&self) -> &'static str;
+
+    fn parse(&self, text: &str) -> Result<Vec<Dependency>>;
+}
+
+static PARSERS: &[&dyn ManifestParser] = &[
+    &npm::PackageJson,
+    &dart::PubspecYaml,
+    &cargo::CargoToml,
+    &gradle::BuildGradle,
+    &python::PyprojectToml,
+    &python::RequirementsTxt,
+    &python::Pipfile,
+];
+
+/// The parser of a manifest file name
+pub fn parser(filename: &str) -> Option<&'static dyn ManifestParser> {
+    PARSERS
+        .iter()
+        .find(|parser| parser.filename() == filename)
+        .copied()
+}
+
+fn using_clean_url<T>(text: T) -> String
+where
+    T: Into<String>,
+{
+    urlencoding::encode(&text.into())
+        .replace('-', "--")
+        .replace('_', "__")
+}
+
+/// Sorts dependencies by name. A package listed both as a dependency and a dev dependency is
+/// kept once, with the version of its dev dependency
+pub fn sort(dependencies: &mut Vec<Dependency>) {
+    dependencies.sort_by(|a, b| {
+        a.name
+            .cmp(&b.name)
+            .then((b.kind == Kind::Dev).cmp(&(a.kind == Kind::Dev)))
+    });
+    dependencies.dedup_by(|a, b| a.name == b.name);
+}
+
+/// Renders a badge for every dependency, in the order the parser returned them
+pub fn render(dependencies: &[Dependency]) -> String {
+    dependencies
+        .iter()
+        .map(|dependency| {
+            format!(
+                "        -   [![{}](https://img.shields.io/badge/{}-{}-{}?style=flat-square)]({})",
+                dependency.name,
+                using_clean_url(&dependency.name),
+                using_clean_url(&dependency.version),
+                dependency.ecosystem.color(),
+                dependency
+                    .ecosystem
+                    .url(&dependency.name, &dependency.version),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cleans_badge_text() {
+        assert_eq!(using_clean_url("serde_json"), "serde__json");
+        assert_eq!(using_clean_url("eslint-plugin"), "eslint--plugin");
+        assert_eq!(using_clean_url("@types/node"), "%40types%2Fnode");
+        assert_eq!(using_clean_url("^1.0.0"), "%5E1.0.0");
+    }
+
+    #[test]
+    fn finds_parser_by_filename() {
+        assert_eq!(parser("Cargo.toml").unwrap().filename(), "Cargo.toml");
+        assert!(parser("cargo.toml").is_none());
+        assert!(parser("README.md").is_none());
+    }
+
+    #[test]
+    fn sorts_and_keeps_dev_version_of_duplicates() {
+        let mut dependencies = vec![
+            Dependency::new("zod", "^3.0.0", Ecosystem::Npm, Kind::Normal),
+            Dependency::new("typescript", "^4.0.0", Ecosystem::Npm, Kind::Normal),
+            Dependency::new("typescript", "^5.0.0", Ecosystem::Npm, Kind::Dev),
+            Dependency::new("react", "^18.2.0", Ecosystem::Npm, Kind::Normal),
+        ];
+        sort(&mut dependencies);
+
+        assert_eq!(
+            dependencies,
+            vec![
+                Dependency::new("react", "^18.2.0", Ecosystem::Npm, Kind::Normal),
+                Dependency::new("typescript", "^5.0.0", Ecosystem::Npm, Kind::Dev),
+                Dependency::new("zod", "^3.0.0", Ecosystem::Npm, Kind::Normal),
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_badges_in_order() {
+        let dependencies = [
+            Dependency::new("zod", "^3.0.0", Ecosystem::Npm, Kind::Normal),
+            Dependency::new("http", "~1.1.0", Ecosystem::Pub, Kind::Normal),
+            Dependency::new("serde_json", "1.0", Ecosystem::Crates, Kind::Normal),
+            Dependency::new("junit:junit", "4.13.2", Ecosystem::Maven, Kind::Dev),
+        ];
+
+        assert_eq!(
+            render(&dependencies),
+            [
+                "        -   [![zod](https://img.shields.io/badge/zod-%5E3.0.0-red?style=flat-square)](https://npmjs.com/package/zod/v/3.0.0)",
+                "        -   [![http](https://img.shields.io/badge/http-~1.1.0-blue?style=flat-square)](https://pub.dev/packages/http/versions/1.1.0)",
+                "        -   [![serde_json](https://img.shields.io/badge/serde__json-1.0-yellow?style=flat-square)](https://crates.io/crates/serde_json/1.0)",
+                "        -   [![junit:junit](https://img.shields.io/badge/junit%3Ajunit-4.13.2-brightgreen?style=flat-square)](https://mvnrepository.com/artifact/junit/junit/4.13.2)",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn renders_nothing_without_dependencies() {
+        assert_eq!(render(&[]), "");
+    }
+}
@@ -0,0 +1,73 @@
+use {
+    super::{sort, Dependency, Ecosystem, Kind, ManifestParser},
+    crate::error::{Error, Result},
+};
+
+pub struct PackageJson;
+
+impl ManifestParser for PackageJson {
+    fn filename(&self) -> &'static str {
+        "package.json"
+    }
+
+    fn parse(&self, text: &str) -> Result<Vec<Dependency>> {
+        let json = serde_json::from_str::<serde_json::Value>(text)
+            .map_err(|err| Error::Parse(format!("Unable to parse package.json\n{}", err)))?;
+
+        let mut dependencies = vec![];
+        for (section, kind) in [
+            ("dependencies", Kind::Normal),
+            ("devDependencies", Kind::Dev),
+        ] {
+            let section = match json.get(section).and_then(|section| section.as_object()) {
+                Some(section) => section,
+                None => continue,
+            };
+
+            for (name, version) in section {
+                if let Some(version) = version.as_str() {
+                    dependencies.push(Dependency::new(name, version, Ecosystem::Npm, kind));
+                }
+            }
+        }
+
+        sort(&mut dependencies);
+        Ok(dependencies)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_dependencies_and_dev_dependencies() {
+        let dependencies = PackageJson
+            .parse(
+                r#"{
+                    "dependencies": { "zod": "^3.0.0", "react": "^18.2.0" },
+                    "devDependencies": { "@types/node": "~20.1.0" }
+                }"#,
+            )
+            .unwrap();
+
+        assert_eq!(
+            dependencies,
+            vec![
+                Dependency::new("@types/node", "~20.1.0", Ecosystem::Npm, Kind::Dev),
+                Dependency::new("react", "^18.2.0", Ecosystem::Npm, Kind::Normal),
+                Dependency::new("zod", "^3.0.0", Ecosystem::Npm, Kind::Normal),
+            ]
+        );
+    }
+
+    #[test]
+    fn allows_missing_sections() {
+        assert!(PackageJson.parse(r#"{ "name": "x" }"#).unwrap().is_empty());
+    }
+
+    #[test]
+    fn fails_on_invalid_json() {
+        assert!(PackageJson.parse("{").is_err());
+    }
+}
@@ -0,0 +1,354 @@
+use {
+    super::{sort, Dependency, Ecosystem, Kind, ManifestParser},
+    crate::error::{Error, Result},
+};
+
+/// The version a PEP 440 specifier like `>=2.31,<3` or a Poetry constraint like `^1.2` is
+/// based on. Exclusions and upper bounds are skipped, since they don't name a usable version
+pub fn version(specifier: &str) -> Option<&str> {
+    specifier
+        .split(',')
+        .map(str::trim)
+        .filter(|clause| !clause.starts_with("!=") && !clause.starts_with('<'))
+        .map(|clause| {
+            clause
+                .trim_start_matches(['=', '~', '^', '>', ' '])
+                .trim_end_matches(".*")
+        })
+        .find(|version| version.starts_with(|char: char| char.is_ascii_digit()))
+}
+
+/// Splits a PEP 508 requirement like `requests[socks]>=2.31; python_version > "3.8"` into its
+/// name and version specifier
+fn requirement(text: &str) -> Option<(&str, String)> {
+    let regex = regex::Regex::new(
+        r"^([A-Za-z0-9][A-Za-z0-9._-]*)\s*(?:\[[^\]]*\])?\s*\(?([^;@()]*)\)?\s*(?:;.*)?$",
+    )
+    .unwrap();
+
+    let captures = regex.captures(text.trim())?;
+    Some((
+        captures.get(1).unwrap().as_str(),
+        captures.get(2).unwrap().as_str().replace(' ', ""),
+    ))
+}
+
+fn push(dependencies: &mut Vec<Dependency>, name: &str, specifier: &str, kind: Kind) {
+    if version(specifier).is_some() {
+        dependencies.push(Dependency::new(name, specifier, Ecosystem::PyPI, kind));
+    }
+}
+
+/// Reads PEP 508 requirement strings from an array
+fn push_requirements(dependencies: &mut Vec<Dependency>, array: Option<&toml::Value>, kind: Kind) {
+    for text in array
+        .and_then(|array| array.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|text| text.as_str())
+    {
+        if let Some((name, specifier)) = requirement(text) {
+            push(dependencies, name, &specifier, kind);
+        }
+    }
+}
+
+/// Reads a table of names to either a version or a table with a version, like Poetry and
+/// Pipfile use
+fn push_table(dependencies: &mut Vec<Dependency>, table: Option<&toml::Value>, kind: Kind) {
+    for (name, specifier) in table
+        .and_then(|table| table.as_table())
+        .into_iter()
+        .flatten()
+    {
+        let specifier = match specifier {
+            toml::Value::String(specifier) => Some(specifier.as_str()),
+            toml::Value::Table(table) => table.get("version").and_then(|version| version.as_str()),
+            _ => None,
+        };
+
+        // Poetry lists the supported Python versions as a dependency
+        if let Some(specifier) = specifier.filter(|_| name != "python") {
+            push(dependencies, name, specifier, kind);
+        }
+    }
+}
+
+fn parse_toml(text: &str, filename: &str) -> Result<toml::Value> {
+    text.parse::<toml::Value>()
+        .map_err(|err| Error::Parse(format!("Unable to parse {}\n{}", filename, err)))
+}
+
+pub struct PyprojectToml;
+
+impl ManifestParser for PyprojectToml {
+    fn filename(&self) -> &'static str {
+        "pyproject.toml"
+    }
+
+    fn parse(&self, text: &str) -> Result<Vec<Dependency>> {
+        let pyproject = parse_toml(text, self.filename())?;
+        let mut dependencies = vec![];
+
+        // PEP 621
+        if let Some(project) = pyproject.get("project") {
+            push_requirements(&mut dependencies, project.get("dependencies"), Kind::Normal);
+            for (_, extra) in project
+                .get("optional-dependencies")
+                .and_then(|extras| extras.as_table())
+                .into_iter()
+                .flatten()
+            {
+                push_requirements(&mut dependencies, Some(extra), Kind::Normal);
+            }
+        }
+
+        // PEP 735
+        for (_, group) in pyproject
+            .get("dependency-groups")
+            .and_then(|groups| groups.as_table())
+            .into_iter()
+            .flatten()
+        {
+            push_requirements(&mut dependencies, Some(group), Kind::Dev);
+        }
+
+        // Poetry
+        if let Some(poetry) = pyproject.get("tool").and_then(|tool| tool.get("poetry")) {
+            push_table(&mut dependencies, poetry.get("dependencies"), Kind::Normal);
+            push_table(&mut dependencies, poetry.get("dev-dependencies"), Kind::Dev);
+            for (_, group) in poetry
+                .get("group")
+                .and_then(|groups| groups.as_table())
+                .into_iter()
+                .flatten()
+            {
+                push_table(&mut dependencies, group.get("dependencies"), Kind::Dev);
+            }
+        }
+
+        sort(&mut dependencies);
+        Ok(dependencies)
+    }
+}
+
+pub struct RequirementsTxt;
+
+impl ManifestParser for RequirementsTxt {
+    fn filename(&self) -> &'static str {
+        "requirements.txt"
+    }
+
+    fn parse(&self, text: &str) -> Result<Vec<Dependency>> {
+        // Lines ending with a backslash continue on the next line, like the hashes pip-compile
+        // writes under every requirement
+        let text = text.replace("\r\n", "\n").replace("\\\n", " ");
+
+        let mut dependencies = vec![];
+        for line in text.lines() {
+            let line = line.split(" #").next().unwrap().trim();
+            // Options of the requirement itself like --hash
+            let line = line.split(" --").next().unwrap().trim();
+
+            // Options like -r and -e, and requirements installed from a path or URL
+            if line.is_empty() || line.starts_with(['#', '-', '.', '/']) || line.contains("://") {
+                continue;
+            }
+
+            if let Some((name, specifier)) = requirement(line) {
+                push(&mut dependencies, name, &specifier, Kind::Normal);
+            }
+        }
+
+        sort(&mut dependencies);
+        Ok(dependencies)
+    }
+}
+
+pub struct Pipfile;
+
+impl ManifestParser for Pipfile {
+    fn filename(&self) -> &'static str {
+        "Pipfile"
+    }
+
+    fn parse(&self, text: &str) -> Result<Vec<Dependency>> {
+        let pipfile = parse_toml(text, self.filename())?;
+        let mut dependencies = vec![];
+
+        push_table(&mut dependencies, pipfile.get("packages"), Kind::Normal);
+        push_table(&mut dependencies, pipfile.get("dev-packages"), Kind::Dev);
+
+        sort(&mut dependencies);
+        Ok(dependencies)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_version_from_pep_440_specifiers() {
+        assert_eq!(version("==2.31.0"), Some("2.31.0"));
+        assert_eq!(version("===1.0"), Some("1.0"));
+        assert_eq!(version("~=8.1"), Some("8.1"));
+        assert_eq!(version(">=2.31,<3"), Some("2.31"));
+        assert_eq!(version("==1.26.*"), Some("1.26"));
+        assert_eq!(version("!=2.0,>=2.3"), Some("2.3"));
+        assert_eq!(version(">1.0"), Some("1.0"));
+    }
+
+    #[test]
+    fn extracts_version_from_poetry_constraints() {
+        assert_eq!(version("^0.27.0"), Some("0.27.0"));
+        assert_eq!(version("~24.1"), Some("24.1"));
+        assert_eq!(version("1.2.3"), Some("1.2.3"));
+    }
+
+    #[test]
+    fn skips_specifiers_without_a_version() {
+        assert_eq!(version("*"), None);
+        assert_eq!(version(""), None);
+        assert_eq!(version("<2"), None);
+        assert_eq!(version("!=1.0"), None);
+    }
+
+    #[test]
+    fn splits_requirements() {
+        assert_eq!(
+            requirement("requests>=2.31"),
+            Some(("requests", ">=2.31".into()))
+        );
+        assert_eq!(
+            requirement("requests[socks,security] >= 2.31 , < 3"),
+            Some(("requests", ">=2.31,<3".into()))
+        );
+        assert_eq!(
+            requirement("numpy==1.26.*; python_version > '3.9'"),
+            Some(("numpy", "==1.26.*".into()))
+        );
+        assert_eq!(
+            requirement("click (~=8.1)"),
+            Some(("click", "~=8.1".into()))
+        );
+        assert_eq!(requirement("rich"), Some(("rich", "".into())));
+        assert_eq!(requirement("pkg @ https://example.com/pkg.zip"), None);
+        assert_eq!(requirement("-r base.txt"), None);
+    }
+
+    fn badges(dependencies: Vec<Dependency>) -> Vec<(String, String, Kind)> {
+        dependencies
+            .into_iter()
+            .map(|dependency| (dependency.name, dependency.version, dependency.kind))
+            .collect()
+    }
+
+    fn badge(name: &str, version: &str, kind: Kind) -> (String, String, Kind) {
+        (name.into(), version.into(), kind)
+    }
+
+    #[test]
+    fn reads_pep_621_and_poetry_dependencies() {
+        let dependencies = PyprojectToml
+            .parse(
+                r#"
+                [project]
+                dependencies = ["requests[socks]>=2.31,<3", "rich", "foo<2"]
+
+                [project.optional-dependencies]
+                web = ["flask!=2.0,>=2.3"]
+
+                [dependency-groups]
+                test = ["pytest>=8"]
+
+                [tool.poetry.dependencies]
+                python = "^3.11"
+                httpx = "^0.27.0"
+                pydantic = { version = ">=2.5", extras = ["email"] }
+                local = { path = "../local" }
+
+                [tool.poetry.group.dev.dependencies]
+                black = "~24.1"
+                "#,
+            )
+            .unwrap();
+
+        assert_eq!(
+            badges(dependencies),
+            vec![
+                badge("black", "~24.1", Kind::Dev),
+                badge("flask", "!=2.0,>=2.3", Kind::Normal),
+                badge("httpx", "^0.27.0", Kind::Normal),
+                badge("pydantic", ">=2.5", Kind::Normal),
+                badge("pytest", ">=8", Kind::Dev),
+                badge("requests", ">=2.31,<3", Kind::Normal),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_pip_compile_output() {
+        let dependencies = RequirementsTxt
+            .parse(
+                r"
+                -r base.txt
+                -e git+https://example.com/pkg.git#egg=pkg
+                requests==2.31.0 \
+                    --hash=sha256:aaa \
+                    --hash=sha256:bbb
+                    # via app
+                Django==5.0.1  # web
+                uvicorn[standard] >= 0.27
+                ./local
+                attrs
+                ",
+            )
+            .unwrap();
+
+        assert_eq!(
+            badges(dependencies),
+            vec![
+                badge("Django", "==5.0.1", Kind::Normal),
+                badge("requests", "==2.31.0", Kind::Normal),
+                badge("uvicorn", ">=0.27", Kind::Normal),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_pipfile_packages() {
+        let dependencies = Pipfile
+            .parse(
+                r#"
+                [packages]
+                flask = "*"
+                sqlalchemy = "==2.0.25"
+                aiohttp = { version = ">=3.9", extras = ["speedups"] }
+
+                [dev-packages]
+                mypy = "~=1.8"
+                "#,
+            )
+            .unwrap();
+
+        assert_eq!(
+            badges(dependencies),
+            vec![
+                badge("aiohttp", ">=3.9", Kind::Normal),
+                badge("mypy", "~=1.8", Kind::Dev),
+                badge("sqlalchemy", "==2.0.25", Kind::Normal),
+            ]
+        );
+    }
+
+    #[test]
+    fn links_to_the_extracted_version() {
+        let dependencies = RequirementsTxt.parse("requests>=2.31,<3\n").unwrap();
+
+        assert_eq!(
+            super::super::render(&dependencies),
+            "        -   [![requests](https://img.shields.io/badge/requests-%3E%3D2.31%2C%3C3-blueviolet?style=flat-square)](https://pypi.org/project/requests/2.31)"
+        );
+    }
+}
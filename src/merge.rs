@@ -0,0 +1,248 @@
+use crate::error::{Error, Result};
+
+/// Deep merges a stored config into the existing local file, keeping the key order and
+/// indentation of the local file. Objects are merged key by key, any other value present on
+/// both sides is taken from the preferred side. JSON may contain comments and trailing commas
+/// like tsconfig.json does, but comments in JSON, YAML and TOML files are not kept
+pub fn merge(stored: &str, local: &str, filename: &str, prefer_local: bool) -> Result<String> {
+    let extension = std::path::Path::new(filename)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default();
+    let indent = indentation(local);
+
+    let merged = match extension {
+        "json" | "jsonc" => {
+            let mut value = parse_json(local, filename)?;
+            merge_json(&mut value, parse_json(stored, filename)?, prefer_local);
+
+            let indent = indent.unwrap_or_else(|| "  ".into());
+            let mut bytes = vec![];
+            let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+            let mut serializer = serde_json::Serializer::with_formatter(&mut bytes, formatter);
+            serde::Serialize::serialize(&value, &mut serializer).map_err(|err| {
+                Error::Parse(format!("Unable to serialize \"{}\"\n{}", filename, err))
+            })?;
+            String::from_utf8(bytes).unwrap()
+        }
+        "yaml" | "yml" => {
+            let mut value = parse_yaml(local, filename)?;
+            merge_yaml(&mut value, parse_yaml(stored, filename)?, prefer_local);
+
+            let text = serde_yaml::to_string(&value).map_err(|err| {
+                Error::Parse(format!("Unable to serialize \"{}\"\n{}", filename, err))
+            })?;
+            match indent {
+                Some(indent) => reindent(&text, 2, &indent),
+                None => text,
+            }
+        }
+        "toml" => {
+            let mut value = parse_toml(local, filename)?;
+            merge_toml(&mut value, parse_toml(stored, filename)?, prefer_local);
+
+            toml::to_string(&value).map_err(|err| {
+                Error::Parse(format!("Unable to serialize \"{}\"\n{}", filename, err))
+            })?
+        }
+        _ => {
+            return Err(Error::Usage(format!(
+                "Unable to merge \"{}\", only JSON, YAML and TOML files can be merged",
+                filename
+            )))
+        }
+    };
+
+    Ok(match local.ends_with('\n') {
+        true => format!("{}\n", merged.trim_end_matches('\n')),
+        false => merged.trim_end_matches('\n').to_string(),
+    })
+}
+
+fn parse_json(text: &str, filename: &str) -> Result<serde_json::Value> {
+    serde_json::from_str(&strip_jsonc(text))
+        .map_err(|err| Error::Parse(format!("Unable to parse \"{}\" as JSON\n{}", filename, err)))
+}
+
+/// Blanks out the comments and trailing commas of JSONC, keeping line and column numbers intact
+/// for parse errors
+fn strip_jsonc(text: &str) -> String {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut output = String::with_capacity(text.len());
+    let mut in_string = false;
+    let mut index = 0;
+
+    while index < chars.len() {
+        let char = chars[index];
+        if in_string {
+            output.push(char);
+            if char == '\\' {
+                if let Some(escaped) = chars.get(index + 1) {
+                    output.push(*escaped);
+                    index += 1;
+                }
+            } else if char == '"' {
+                in_string = false;
+            }
+            index += 1;
+            continue;
+        }
+
+        match (char, chars.get(index + 1)) {
+            ('"', _) => {
+                in_string = true;
+                output.push(char);
+                index += 1;
+            }
+            ('/', Some('/')) => {
+                while index < chars.len() && chars[index] != '\n' {
+                    output.push(' ');
+                    index += 1;
+                }
+            }
+            ('/', Some('*')) => {
+                let end = (index + 2..chars.len().saturating_sub(1))
+                    .find(|&end| chars[end] == '*' && chars[end + 1] == '/')
+                    .map_or(chars.len(), |end| end + 2);
+                for char in &chars[index..end] {
+                    output.push(if *char == '\n' { '\n' } else { ' ' });
+                }
+                index = end;
+            }
+            _ => {
+                output.push(char);
+                index += 1;
+            }
+        }
+    }
+
+    // Comments are blanked out by now, so any comma followed by a closing bracket is trailing
+    let chars = output.chars().collect::<Vec<_>>();
+    let mut in_string = false;
+    let mut escaped = false;
+    chars
+        .iter()
+        .enumerate()
+        .map(|(index, &char)| {
+            if in_string {
+                in_string = escaped || char != '"';
+                escaped = !escaped && char == '\\';
+                return char;
+            }
+
+            in_string = char == '"';
+            let trailing = char == ','
+                && chars[index + 1..]
+                    .iter()
+                    .find(|char| !char.is_whitespace())
+                    .is_some_and(|char| *char == '}' || *char == ']');
+            match trailing {
+                true => ' ',
+                false => char,
+            }
+        })
+        .collect()
+}
+
+fn parse_yaml(text: &str, filename: &str) -> Result<serde_yaml::Value> {
+    serde_yaml::from_str(text)
+        .map_err(|err| Error::Parse(format!("Unable to parse \"{}\" as YAML\n{}", filename, err)))
+}
+
+fn parse_toml(text: &str, filename: &str) -> Result<toml::Value> {
+    text.parse()
+        .map_err(|err| Error::Parse(format!("Unable to parse \"{}\" as TOML\n{}", filename, err)))
+}
+
+fn merge_json(local: &mut serde_json::Value, stored: serde_json::Value, prefer_local: bool) {
+    match (local, stored) {
+        (serde_json::Value::Object(local), serde_json::Value::Object(stored)) => {
+            for (key, value) in stored {
+                match local.get_mut(&key) {
+                    Some(existing) => merge_json(existing, value, prefer_local),
+                    None => {
+                        local.insert(key, value);
+                    }
+                }
+            }
+        }
+        (local, stored) => {
+            if !prefer_local {
+                *local = stored;
+            }
+        }
+    }
+}
+
+fn merge_yaml(local: &mut serde_yaml::Value, stored: serde_yaml::Value, prefer_local: bool) {
+    match (local, stored) {
+        (serde_yaml::Value::Mapping(local), serde_yaml::Value::Mapping(stored)) => {
+            for (key, value) in stored {
+                match local.get_mut(&key) {
+                    Some(existing) => merge_yaml(existing, value, prefer_local),
+                    None => {
+                        local.insert(key, value);
+                    }
+                }
+            }
+        }
+        (local, stored) => {
+            if !prefer_local {
+                *local = stored;
+            }
+        }
+    }
+}
+
+fn merge_toml(local: &mut toml::Value, stored: toml::Value, prefer_local: bool) {
+    match (local, stored) {
+        (toml::Value::Table(local), toml::Value::Table(stored)) => {
+            for (key, value) in stored {
+                match local.get_mut(&key) {
+                    Some(existing) => merge_toml(existing, value, prefer_local),
+                    None => {
+                        local.insert(key, value);
+                    }
+                }
+            }
+        }
+        (local, stored) => {
+            if !prefer_local {
+                *local = stored;
+            }
+        }
+    }
+}
+
+/// The unit of indentation of a file, either a tab or the smallest number of leading spaces
+fn indentation(text: &str) -> Option<String> {
+    let mut spaces = None;
+    for line in text.lines().filter(|line| !line.trim().is_empty()) {
+        if line.starts_with('\t') {
+            return Some("\t".into());
+        }
+
+        let count = line.len() - line.trim_start_matches(' ').len();
+        if count > 0 && spaces.is_none_or(|spaces| count < spaces) {
+            spaces = Some(count);
+        }
+    }
+
+    spaces.map(|spaces| " ".repeat(spaces))
+}
+
+/// Replaces every `width` leading spaces of each line with `indent`
+fn reindent(text: &str, width: usize, indent: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let count = line.len() - line.trim_start_matches(' ').len();
+            format!(
+                "{}{}{}",
+                indent.repeat(count / width),
+                " ".repeat(count % width),
+                &line[count..]
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
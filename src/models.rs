@@ -1,11 +1,36 @@
 use diesel::prelude::*;
 
+#[derive(Queryable, Insertable)]
+#[diesel(table_name = super::schema::bundles)]
+pub struct Bundle {
+    pub name: String,
+    pub position: i32,
+    /// A config shorthand, or another bundle's name prefixed with `@`
+    pub member: String,
+}
+
 #[derive(Queryable, Insertable)]
 #[diesel(table_name = super::schema::configs)]
 pub struct Config {
-    pub filename: String,
     pub shorthand: String,
+    /// Target path relative to the project folder, may include subdirectories
+    pub path: String,
+    pub content: String,
+    /// TOML table describing how to prompt for each template variable
+    pub variables: Option<String>,
+}
+
+#[derive(Queryable, Insertable)]
+#[diesel(table_name = super::schema::config_versions)]
+pub struct ConfigVersion {
+    pub shorthand: String,
+    /// Counts up from 1 for every change to any file of the shorthand
+    pub revision: i32,
+    pub path: String,
     pub content: String,
+    pub message: String,
+    /// Unix timestamp of when the revision was recorded
+    pub created_at: i64,
 }
 
 #[derive(Queryable, Insertable)]
@@ -14,4 +39,30 @@ pub struct Secret {
     pub project: String,
     pub path: String,
     pub content: String,
+    pub remote: Option<String>,
+    /// Unix timestamp of when the content was last changed
+    pub updated_at: i64,
+    /// Unix timestamp of when the content was last known to match the file on disk
+    pub synced_at: i64,
+}
+
+#[derive(Queryable, Insertable)]
+#[diesel(table_name = super::schema::secret_versions)]
+pub struct SecretVersion {
+    pub project: String,
+    pub path: String,
+    /// Counts up from 1 for every change to the secret
+    pub revision: i32,
+    /// Encrypted like `Secret::content`
+    pub content: String,
+    /// Unix timestamp of when the revision was recorded
+    pub created_at: i64,
+}
+
+#[derive(Queryable, Insertable)]
+#[diesel(table_name = super::schema::vault)]
+pub struct Vault {
+    pub id: i32,
+    pub salt: String,
+    pub verifier: String,
 }
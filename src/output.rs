@@ -1,28 +1,28 @@
 #[macro_export]
 macro_rules! error {
     ($message:expr) => {
-        println!("[ERROR] {}", $message)
+        eprintln!("[ERROR] {}", $message)
     };
     ($message:expr, $var:expr) => {
-        println!("[ERROR] {} \"{}\"", $message, $var)
+        eprintln!("[ERROR] {} \"{}\"", $message, $var)
     };
     ($message:expr; $err:ident) => {
         error!($message);
-        println!("{}", $err)
+        eprintln!("{}", $err)
     };
     ($message:expr, $var:expr; $err:ident) => {
         error!($message, $var);
-        println!("{}", $err)
+        eprintln!("{}", $err)
     };
 }
 
 #[macro_export]
 macro_rules! warn {
     ($message:expr) => {
-        println!("[WARN] {}", $message)
+        eprintln!("[WARN] {}", $message)
     };
     ($message:expr, $var:expr) => {
-        println!("[WARN] {} \"{}\"", $message, $var)
+        eprintln!("[WARN] {} \"{}\"", $message, $var)
     };
 }
 
@@ -34,4 +34,158 @@ macro_rules! success {
 	($message:expr, $var:expr) => {
 		println!("[SUCCESS] {} \"{}\"", $message, $var)
 	};
-}
\ No newline at end of file
+}
+use {
+    crate::error::{self, Error},
+    std::io::Write,
+};
+
+#[derive(Clone, Copy, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    Table,
+    Json,
+    Plain,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "plain" => Ok(Format::Plain),
+            _ => Err(format!(
+                "Unknown output format \"{}\", expected json, table or plain",
+                format
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Field {
+    Shorthand,
+    Filename,
+    Size,
+    Status,
+    Members,
+    Revision,
+    Message,
+    Date,
+}
+
+impl Field {
+    fn key(self) -> &'static str {
+        match self {
+            Field::Shorthand => "shorthand",
+            Field::Filename => "filename",
+            Field::Size => "size",
+            Field::Status => "status",
+            Field::Members => "members",
+            Field::Revision => "revision",
+            Field::Message => "message",
+            Field::Date => "date",
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Record {
+    pub shorthand: String,
+    pub filename: String,
+    pub size: usize,
+    pub status: String,
+    pub members: Vec<String>,
+    pub revision: i32,
+    pub message: String,
+    pub date: String,
+}
+
+impl Record {
+    fn value(&self, field: Field) -> serde_json::Value {
+        match field {
+            Field::Shorthand => self.shorthand.clone().into(),
+            Field::Filename => self.filename.clone().into(),
+            Field::Size => self.size.into(),
+            Field::Status => self.status.clone().into(),
+            Field::Members => self.members.clone().into(),
+            Field::Revision => self.revision.into(),
+            Field::Message => self.message.clone().into(),
+            Field::Date => self.date.clone().into(),
+        }
+    }
+
+    fn cell(&self, field: Field) -> String {
+        match self.value(field) {
+            serde_json::Value::String(string) => string,
+            serde_json::Value::Array(_) => self.members.join(" "),
+            value => value.to_string(),
+        }
+    }
+}
+
+/// Fails on errors writing to stdout, except when it is a pipe that was closed early like by
+/// `| head`, which is not an error of the command
+fn printed<T>(result: std::io::Result<T>) -> error::Result<()> {
+    match result {
+        Err(err) if err.kind() != std::io::ErrorKind::BrokenPipe => {
+            Err(Error::Io("Unable to write to stdout".into(), err))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Prints a line to stdout without panicking if the reader went away
+pub fn print(text: &str) -> error::Result<()> {
+    printed(writeln!(std::io::stdout().lock(), "{}", text))
+}
+
+/// Prints records in the global output format, `columns` pairs table titles with record fields
+pub fn print_records(columns: &[(&str, Field)], records: &[Record]) -> error::Result<()> {
+    match crate::settings::get().format {
+        Format::Table => {
+            let mut table = prettytable::Table::new();
+            table.set_titles(columns.iter().map(|(title, _)| title).collect());
+
+            for record in records {
+                table.add_row(
+                    columns
+                        .iter()
+                        .map(|(_, field)| record.cell(*field))
+                        .collect(),
+                );
+            }
+
+            printed(table.print(&mut std::io::stdout().lock()))
+        }
+        Format::Json => {
+            let records = records
+                .iter()
+                .map(|record| {
+                    columns
+                        .iter()
+                        .map(|(_, field)| (field.key().to_string(), record.value(*field)))
+                        .collect::<serde_json::Map<_, _>>()
+                })
+                .collect::<Vec<_>>();
+
+            print(&serde_json::to_string_pretty(&records).unwrap())
+        }
+        Format::Plain => {
+            let mut stdout = std::io::stdout().lock();
+            printed(records.iter().try_for_each(|record| {
+                writeln!(
+                    stdout,
+                    "{}",
+                    columns
+                        .iter()
+                        .map(|(_, field)| record.cell(*field))
+                        .collect::<Vec<_>>()
+                        .join("\t")
+                )
+            }))
+        }
+    }
+}
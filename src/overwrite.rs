@@ -0,0 +1,148 @@
+use {
+    crate::{
+        diff,
+        error::{Error, Result},
+        prompt, warn,
+    },
+    std::path::Path,
+};
+
+/// What happened to a file written through [`Overwrite::write`]
+#[derive(Clone, Copy, PartialEq)]
+pub enum Outcome {
+    Created,
+    Changed,
+    Unchanged,
+    /// The user chose not to overwrite the file
+    Kept,
+    /// The file differs and could not be overwritten without `--force`
+    Refused,
+}
+
+/// Guards commands that write files from overwriting local changes
+pub struct Overwrite {
+    dry_run: bool,
+    force: bool,
+    backup: bool,
+    refused: usize,
+}
+
+impl Overwrite {
+    /// Adds the `--dry-run`, `--force` and `--backup` flags to a command
+    pub fn flags(command: seahorse::Command) -> seahorse::Command {
+        command
+            .flag(
+                seahorse::Flag::new("dry-run", seahorse::FlagType::Bool)
+                    .description("Show which files would be created or changed without writing"),
+            )
+            .flag(
+                seahorse::Flag::new("force", seahorse::FlagType::Bool)
+                    .description("Overwrite files that differ without asking"),
+            )
+            .flag(
+                seahorse::Flag::new("backup", seahorse::FlagType::Bool)
+                    .description("Keep a .bak copy of every file that is overwritten"),
+            )
+    }
+
+    pub fn new(context: &seahorse::Context) -> Overwrite {
+        Overwrite {
+            dry_run: context.bool_flag("dry-run"),
+            force: context.bool_flag("force"),
+            backup: context.bool_flag("backup"),
+            refused: 0,
+        }
+    }
+
+    pub fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Writes `content` to `path`, asking before overwriting a file that differs
+    pub fn write(&mut self, path: &Path, name: &str, content: &str) -> Result<Outcome> {
+        let existing = std::fs::read_to_string(path).ok();
+        let outcome = match &existing {
+            None => Outcome::Created,
+            Some(existing) if existing == content => Outcome::Unchanged,
+            Some(_) => Outcome::Changed,
+        };
+
+        if self.dry_run {
+            match outcome {
+                Outcome::Created => println!("Would create \"{}\"", name),
+                Outcome::Unchanged => println!("Would leave \"{}\" unchanged", name),
+                _ => {
+                    println!("Would change \"{}\"", name);
+                    print!(
+                        "{}",
+                        diff::unified(
+                            existing.as_deref().unwrap_or_default(),
+                            content,
+                            &format!("{} (local)", name),
+                            &format!("{} (stored)", name),
+                        )
+                    );
+                }
+            }
+            return Ok(outcome);
+        }
+
+        if outcome == Outcome::Unchanged {
+            return Ok(outcome);
+        }
+
+        if outcome == Outcome::Changed && !self.force {
+            if !prompt::interactive() {
+                warn!("Refused to overwrite changed file", name);
+                self.refused += 1;
+                return Ok(Outcome::Refused);
+            }
+
+            print!(
+                "{}",
+                diff::unified(
+                    existing.as_deref().unwrap_or_default(),
+                    content,
+                    &format!("{} (local)", name),
+                    &format!("{} (stored)", name),
+                )
+            );
+            if prompt::choose(&format!("Overwrite \"{}\"?", name), &["yes", "no"])? == 'n' {
+                warn!("Kept local file", name);
+                return Ok(Outcome::Kept);
+            }
+        }
+
+        if outcome == Outcome::Changed && self.backup {
+            let backup = format!("{}.bak", path.display());
+            std::fs::copy(path, &backup)
+                .map_err(|err| Error::Io(format!("Unable to back up file \"{}\"", name), err))?;
+        }
+
+        if let Some(parent) = path.parent().filter(|parent| !parent.exists()) {
+            std::fs::create_dir_all(parent).map_err(|err| {
+                Error::Io(
+                    format!("Unable to create folder \"{}\"", parent.display()),
+                    err,
+                )
+            })?;
+        }
+
+        std::fs::write(path, content)
+            .map_err(|err| Error::Io(format!("Unable to write to file \"{}\"", name), err))?;
+
+        Ok(outcome)
+    }
+
+    /// Fails if any file was left as it is because it differs and `--force` was not given
+    pub fn finish(self) -> Result<()> {
+        if self.refused > 0 {
+            return Err(Error::Conflict(format!(
+                "{} changed file(s) were not overwritten, use --force to overwrite them",
+                self.refused
+            )));
+        }
+
+        Ok(())
+    }
+}
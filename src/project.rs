@@ -0,0 +1,122 @@
+use crate::{
+    settings::{self, Detection, ProjectKey},
+    warn,
+};
+
+pub struct Location {
+    /// Name of the project folder
+    pub project: String,
+    /// Normalized origin remote URL, only resolved when projects are keyed by remote
+    pub remote: Option<String>,
+    /// Absolute path to the project folder
+    pub root: std::path::PathBuf,
+    /// Path of the current working directory relative to the project folder
+    pub folder: Option<String>,
+}
+
+impl Location {
+    /// What the secrets of the project are stored under, the remote if there is one so that
+    /// projects with the same folder name cannot overwrite each other
+    pub fn key(&self) -> String {
+        self.remote.clone().unwrap_or_else(|| self.project.clone())
+    }
+}
+
+fn relative_folder(root: &std::path::Path, cwd: &std::path::Path) -> Option<String> {
+    let folder = cwd.strip_prefix(root).ok()?;
+    if folder.as_os_str().is_empty() {
+        None
+    } else {
+        Some(folder.to_str()?.replace('\\', "/"))
+    }
+}
+
+fn locate_in_roots(cwd: &std::path::Path) -> Option<Location> {
+    settings::get().projects.roots.iter().find_map(|root| {
+        let root = root.canonicalize().unwrap_or_else(|_| root.clone());
+        let project = cwd
+            .strip_prefix(&root)
+            .ok()?
+            .components()
+            .next()?
+            .as_os_str()
+            .to_str()?
+            .to_string();
+
+        let root = root.join(&project);
+        Some(Location {
+            folder: relative_folder(&root, cwd),
+            project,
+            remote: None,
+            root,
+        })
+    })
+}
+
+fn locate_in_git(cwd: &std::path::Path) -> Option<Location> {
+    let root = cwd.ancestors().find(|dir| dir.join(".git").exists())?;
+
+    Some(Location {
+        project: root.file_name()?.to_str()?.to_string(),
+        remote: None,
+        root: root.to_path_buf(),
+        folder: relative_folder(root, cwd),
+    })
+}
+
+pub fn locate() -> Option<Location> {
+    let cwd = std::env::current_dir().ok()?;
+    let mut location = match settings::get().projects.detection {
+        Detection::Roots => locate_in_roots(&cwd),
+        Detection::Git => locate_in_git(&cwd),
+    }?;
+
+    if settings::get().projects.key == ProjectKey::Remote {
+        location.remote = origin_remote(&location.root);
+        if location.remote.is_none() {
+            warn!(
+                "No origin remote found, using folder name",
+                location.project
+            );
+        }
+    }
+
+    Some(location)
+}
+
+pub fn origin_remote(root: &std::path::Path) -> Option<String> {
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(["remote", "get-url", "origin"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    normalize_remote(String::from_utf8(output.stdout).ok()?.trim())
+}
+
+/// Reduces the different ways of writing a remote URL to `host/owner/repo`
+pub fn normalize_remote(url: &str) -> Option<String> {
+    let (host, path) = match url.split_once("://") {
+        Some((_, rest)) => {
+            let (authority, path) = rest.split_once('/')?;
+            (authority.rsplit('@').next()?.split(':').next()?, path)
+        }
+        None => {
+            let (authority, path) = url.split_once(':')?;
+            (authority.rsplit('@').next()?, path)
+        }
+    };
+
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    if host.is_empty() || path.is_empty() {
+        return None;
+    }
+
+    Some(format!("{}/{}", host.to_lowercase(), path))
+}
@@ -0,0 +1,67 @@
+use {
+    crate::error::{Error, Result},
+    std::io::{IsTerminal, Write},
+};
+
+/// Whether there is a user at the terminal to answer prompts
+pub fn interactive() -> bool {
+    std::io::stdin().is_terminal()
+}
+
+/// Prints the question and reads a single trimmed line of input
+pub fn ask(question: &str) -> Result<String> {
+    print!("{}: ", question);
+    std::io::stdout().flush().unwrap();
+
+    let mut answer = String::new();
+    let read = std::io::stdin()
+        .read_line(&mut answer)
+        .map_err(|err| Error::Io("Unable to read from stdin".into(), err))?;
+    if read == 0 {
+        return Err(Error::Usage(format!("No answer given to \"{}\"", question)));
+    }
+
+    Ok(answer.trim().to_string())
+}
+
+/// Asks until the answer is the first letter of one of the options
+pub fn choose(question: &str, options: &[&str]) -> Result<char> {
+    let hint = options
+        .iter()
+        .map(|option| format!("[{}]{}", &option[..1], &option[1..]))
+        .collect::<Vec<_>>()
+        .join("/");
+
+    loop {
+        let answer = ask(&format!("{} {}", question, hint))?.to_lowercase();
+        if let Some(option) = options
+            .iter()
+            .find(|option| !answer.is_empty() && option.starts_with(&answer))
+        {
+            return Ok(option.chars().next().unwrap());
+        }
+    }
+}
+
+/// Asks until the answer is one of the choices, if any, an empty answer picks the default
+pub fn ask_with(question: &str, default: Option<&str>, choices: &[String]) -> Result<String> {
+    let mut question = question.to_string();
+    if !choices.is_empty() {
+        question = format!("{} ({})", question, choices.join("/"));
+    }
+    if let Some(default) = default {
+        question = format!("{} [{}]", question, default);
+    }
+
+    loop {
+        let answer = ask(&question)?;
+        let answer = match default {
+            Some(default) if answer.is_empty() => default.to_string(),
+            _ => answer,
+        };
+
+        if choices.is_empty() || choices.contains(&answer) {
+            return Ok(answer);
+        }
+    }
+}
@@ -1,10 +1,40 @@
 // @generated automatically by Diesel CLI.
 
 diesel::table! {
-    configs (filename) {
-        filename -> Text,
+    bundles (name, position) {
+        name -> Text,
+        position -> Integer,
+        member -> Text,
+    }
+}
+
+diesel::table! {
+    config_versions (shorthand, revision, path) {
+        shorthand -> Text,
+        revision -> Integer,
+        path -> Text,
+        content -> Text,
+        message -> Text,
+        created_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    configs (shorthand, path) {
         shorthand -> Text,
+        path -> Text,
         content -> Text,
+        variables -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    secret_versions (project, path, revision) {
+        project -> Text,
+        path -> Text,
+        revision -> Integer,
+        content -> Text,
+        created_at -> BigInt,
     }
 }
 
@@ -13,7 +43,18 @@ diesel::table! {
         project -> Text,
         path -> Text,
         content -> Text,
+        remote -> Nullable<Text>,
+        updated_at -> BigInt,
+        synced_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    vault (id) {
+        id -> Integer,
+        salt -> Text,
+        verifier -> Text,
     }
 }
 
-diesel::allow_tables_to_appear_in_same_query!(configs, secrets);
+diesel::allow_tables_to_appear_in_same_query!(bundles, config_versions, configs, secrets, vault,);
@@ -0,0 +1,245 @@
+//! Settings are layered, later sources override earlier ones:
+//! defaults, `$XDG_CONFIG_HOME/nova/config.toml`, environment variables, global flags
+
+use {
+    crate::{error::Error, output::Format},
+    serde::Deserialize,
+};
+
+static SETTINGS: std::sync::OnceLock<Settings> = std::sync::OnceLock::new();
+
+pub struct Settings {
+    pub database: std::path::PathBuf,
+    pub projects: Projects,
+    pub format: Format,
+    pub secrets: Secrets,
+    /// Used when neither `$VISUAL` nor `$EDITOR` are set
+    pub editor: String,
+}
+
+pub struct Projects {
+    pub roots: Vec<std::path::PathBuf>,
+    pub detection: Detection,
+    pub key: ProjectKey,
+}
+
+pub struct Secrets {
+    /// Number of versions of each secret kept in its history
+    pub history: usize,
+    /// Overrides of `history` by project folder name or normalized remote URL
+    pub projects: std::collections::HashMap<String, usize>,
+}
+
+impl Secrets {
+    /// Number of versions kept for the secrets of a project, the remote is checked first
+    pub fn history(&self, project: &str, remote: Option<&str>) -> usize {
+        remote
+            .and_then(|remote| self.projects.get(remote))
+            .or_else(|| self.projects.get(project))
+            .copied()
+            .unwrap_or(self.history)
+    }
+}
+
+/// How the project of the current working directory is found
+#[derive(Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Detection {
+    /// The first folder under one of the project roots
+    Roots,
+    /// The nearest parent folder that is a git repository
+    Git,
+}
+
+/// What the secrets of a project are looked up by
+#[derive(Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectKey {
+    /// The project folder name
+    Name,
+    /// The normalized origin remote URL, falling back to the project folder name
+    Remote,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SettingsFile {
+    database: Option<String>,
+    projects: ProjectsFile,
+    format: Option<Format>,
+    secrets: SecretsFile,
+    editor: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ProjectsFile {
+    roots: Option<Vec<String>>,
+    detection: Option<Detection>,
+    key: Option<ProjectKey>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SecretsFile {
+    history: Option<usize>,
+    projects: std::collections::HashMap<String, usize>,
+}
+
+fn home_dir() -> std::path::PathBuf {
+    std::env::var_os("HOME")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| std::path::PathBuf::from("/"))
+}
+
+fn xdg_dir(variable: &str, fallback: &str) -> std::path::PathBuf {
+    match std::env::var_os(variable) {
+        Some(dir) if std::path::Path::new(&dir).is_absolute() => std::path::PathBuf::from(dir),
+        _ => home_dir().join(fallback),
+    }
+}
+
+pub fn expand_path(path: &str) -> std::path::PathBuf {
+    if path == "~" {
+        home_dir()
+    } else if let Some(rest) = path.strip_prefix("~/") {
+        home_dir().join(rest)
+    } else {
+        std::path::PathBuf::from(path)
+    }
+}
+
+pub fn config_dir() -> std::path::PathBuf {
+    xdg_dir("XDG_CONFIG_HOME", ".config").join("nova")
+}
+
+pub fn data_dir() -> std::path::PathBuf {
+    xdg_dir("XDG_DATA_HOME", ".local/share").join("nova")
+}
+
+fn read_settings_file() -> Result<SettingsFile, Error> {
+    let path = config_dir().join("config.toml");
+    match std::fs::read_to_string(&path) {
+        Ok(text) => toml::from_str::<SettingsFile>(&text).map_err(|err| {
+            Error::Settings(format!(
+                "Unable to parse settings file \"{}\"\n{}",
+                path.display(),
+                err
+            ))
+        }),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(SettingsFile::default()),
+        Err(err) => Err(Error::Io(
+            format!("Unable to read settings file \"{}\"", path.display()),
+            err,
+        )),
+    }
+}
+
+/// Global flags are read anywhere before a `--` argument, since no subcommand defines them.
+/// Arguments after `--` are passed through untouched
+fn global_flags_end(args: &[String]) -> usize {
+    args.iter()
+        .position(|arg| arg == "--")
+        .unwrap_or(args.len())
+}
+
+fn take_flag(args: &mut Vec<String>, name: &str) -> Result<Option<String>, Error> {
+    let flag = format!("--{}", name);
+    let prefix = format!("--{}=", name);
+
+    let end = global_flags_end(args);
+    let index = match args[..end]
+        .iter()
+        .position(|arg| arg == &flag || arg.starts_with(&prefix))
+    {
+        Some(index) => index,
+        None => return Ok(None),
+    };
+    let arg = args.remove(index);
+
+    match arg.strip_prefix(&prefix) {
+        Some(value) => Ok(Some(value.to_string())),
+        None if index < end - 1 => Ok(Some(args.remove(index))),
+        None => Err(Error::Usage(format!(
+            "Please provide a value for global flag \"{}\"",
+            flag
+        ))),
+    }
+}
+
+/// Strips global flags from the arguments after reading them
+pub fn init(mut args: Vec<String>) -> Result<Vec<String>, Error> {
+    let file = read_settings_file()?;
+
+    let mut database = data_dir().join("nova.db");
+    if let Some(path) = file.database {
+        database = expand_path(&path);
+    }
+    if let Some(path) = std::env::var("NOVA_DB")
+        .ok()
+        .filter(|path| !path.is_empty())
+    {
+        database = expand_path(&path);
+    }
+    if let Some(path) = take_flag(&mut args, "db")? {
+        database = expand_path(&path);
+    }
+
+    let mut roots = vec![home_dir().join("Projects")];
+    if let Some(paths) = file.projects.roots {
+        roots = paths.iter().map(|path| expand_path(path)).collect();
+    }
+    if let Some(paths) = std::env::var("NOVA_PROJECTS")
+        .ok()
+        .filter(|paths| !paths.is_empty())
+    {
+        roots = paths.split(':').map(expand_path).collect();
+    }
+
+    let projects = Projects {
+        roots,
+        detection: file.projects.detection.unwrap_or(Detection::Roots),
+        key: file.projects.key.unwrap_or(ProjectKey::Name),
+    };
+
+    let mut format = file.format.unwrap_or(Format::Table);
+    if let Some(value) = take_flag(&mut args, "format")? {
+        format = value.parse().map_err(Error::Usage)?;
+    }
+
+    let secrets = Secrets {
+        history: file.secrets.history.unwrap_or(10),
+        projects: file.secrets.projects,
+    };
+    // The current content of a secret is its newest revision, so at least that one is kept
+    if let Some(project) = secrets.projects.iter().find(|(_, history)| **history < 1) {
+        return Err(Error::Settings(format!(
+            "History length of project \"{}\" must be at least 1",
+            project.0
+        )));
+    }
+    if secrets.history < 1 {
+        return Err(Error::Settings(
+            "Secrets history length must be at least 1".into(),
+        ));
+    }
+
+    if SETTINGS
+        .set(Settings {
+            database,
+            projects,
+            format,
+            secrets,
+            editor: file.editor.unwrap_or_else(|| "vi".into()),
+        })
+        .is_err()
+    {
+        panic!("Settings were initialized twice");
+    }
+
+    Ok(args)
+}
+
+pub fn get() -> &'static Settings {
+    SETTINGS.get().expect("Settings were not initialized")
+}
@@ -0,0 +1,194 @@
+use {
+    crate::{
+        error::{Error, Result},
+        prompt, time,
+    },
+    serde::Deserialize,
+};
+
+/// Matches `{{name}}`, leaving `${{ ... }}` expressions used by GitHub Actions untouched
+static PATTERN: &str = r"(\\|\$)?\{\{\s*([A-Za-z_][A-Za-z0-9_.]*)\s*\}\}";
+
+/// How to prompt for a variable, declared in the metadata stored next to a config
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Prompt {
+    pub prompt: Option<String>,
+    pub default: Option<String>,
+    pub choices: Vec<String>,
+}
+
+pub type Prompts = std::collections::BTreeMap<String, Prompt>;
+
+pub fn parse_prompts(metadata: &str) -> Result<Prompts> {
+    toml::from_str::<Prompts>(metadata)
+        .map_err(|err| Error::Parse(format!("Unable to parse variables metadata\n{}", err)))
+}
+
+/// Resolves template variables from `--var` flags, the environment, git config and the cwd,
+/// then from the user at the terminal
+pub struct Variables {
+    overrides: std::collections::HashMap<String, String>,
+}
+
+impl Variables {
+    /// Takes every `--var key=value` pair out of the arguments
+    pub fn take(args: &mut Vec<String>) -> Result<Variables> {
+        let mut overrides = std::collections::HashMap::new();
+
+        while let Some(index) = args.iter().position(|arg| arg == "--var") {
+            args.remove(index);
+            if index >= args.len() {
+                return Err(Error::Usage(
+                    "Please provide a key=value after --var".into(),
+                ));
+            }
+
+            let pair = args.remove(index);
+            match pair.split_once('=') {
+                Some((key, value)) if !key.is_empty() => {
+                    overrides.insert(key.to_string(), value.to_string());
+                }
+                _ => {
+                    return Err(Error::Usage(format!(
+                        "Invalid template variable \"{}\", expected key=value",
+                        pair
+                    )))
+                }
+            }
+        }
+
+        Ok(Variables { overrides })
+    }
+
+    fn git_config(key: &str) -> Option<String> {
+        let output = std::process::Command::new("git")
+            .args(["config", "--get", key])
+            .output()
+            .ok()?;
+
+        Some(String::from_utf8(output.stdout).ok()?.trim().to_string())
+            .filter(|value| output.status.success() && !value.is_empty())
+    }
+
+    fn infer(&self, name: &str) -> Option<String> {
+        if let Some(value) = self.overrides.get(name) {
+            return Some(value.clone());
+        }
+
+        if let Some(variable) = name.strip_prefix("env.") {
+            return std::env::var(variable).ok();
+        }
+
+        let (year, month, day) = time::date(time::now());
+        match name {
+            "project_name" => std::env::current_dir()
+                .ok()?
+                .file_name()?
+                .to_str()
+                .map(String::from),
+            "year" => Some(year.to_string()),
+            "date" => Some(format!("{:04}-{:02}-{:02}", year, month, day)),
+            "author" => Variables::git_config("user.name"),
+            "email" => Variables::git_config("user.email"),
+            _ => None,
+        }
+    }
+
+    /// Infers the variable, otherwise asks the user and remembers the answer for other configs
+    fn resolve(&mut self, name: &str, prompt: Option<&Prompt>) -> Result<Option<String>> {
+        if let Some(value) = self.infer(name) {
+            return Ok(Some(value));
+        }
+
+        let default = prompt.and_then(|prompt| prompt.default.as_deref());
+        let value = if prompt::interactive() {
+            let question = prompt
+                .and_then(|prompt| prompt.prompt.clone())
+                .unwrap_or_else(|| name.to_string());
+            let choices = prompt.map(|prompt| &prompt.choices[..]).unwrap_or_default();
+            prompt::ask_with(&question, default, choices)?
+        } else {
+            match default {
+                Some(default) => default.to_string(),
+                None => return Ok(None),
+            }
+        };
+
+        self.overrides.insert(name.to_string(), value.clone());
+        Ok(Some(value))
+    }
+}
+
+/// Names of every template variable used in the content, in order of appearance
+fn names(content: &str) -> Vec<String> {
+    let mut names = vec![];
+    for captures in regex::Regex::new(PATTERN).unwrap().captures_iter(content) {
+        let name = captures[2].to_string();
+        if captures.get(1).is_none() && !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Substitutes every `{{name}}` in the content, `\{{name}}` escapes a literal `{{name}}`
+pub fn render(
+    content: &str,
+    filename: &str,
+    prompts: &Prompts,
+    variables: &mut Variables,
+) -> Result<String> {
+    let mut values = std::collections::HashMap::new();
+    let mut unresolved = vec![];
+    for name in names(content) {
+        match variables.resolve(&name, prompts.get(&name))? {
+            Some(value) => {
+                values.insert(name, value);
+            }
+            None => unresolved.push(format!("\"{}\"", name)),
+        }
+    }
+
+    if !unresolved.is_empty() {
+        return Err(Error::Usage(format!(
+            "Unresolved template variable(s) {} in \"{}\", provide them with --var key=value",
+            unresolved.join(", "),
+            filename
+        )));
+    }
+
+    Ok(regex::Regex::new(PATTERN)
+        .unwrap()
+        .replace_all(content, |captures: &regex::Captures| {
+            match captures.get(1).map(|prefix| prefix.as_str()) {
+                Some("$") => captures[0].to_string(),
+                Some(_) => captures[0][1..].to_string(),
+                None => values[&captures[2]].clone(),
+            }
+        })
+        .to_string())
+}
+
+/// Whether the content could have been rendered from the template, whatever the variables were
+pub fn matches(template: &str, content: &str) -> bool {
+    let mut expression = String::from("^");
+    let mut rest = 0;
+    for captures in regex::Regex::new(PATTERN).unwrap().captures_iter(template) {
+        let whole = captures.get(0).unwrap();
+        expression.push_str(&regex::escape(&template[rest..whole.start()]));
+        expression.push_str(&match captures.get(1).map(|prefix| prefix.as_str()) {
+            Some("$") => regex::escape(whole.as_str()),
+            Some(_) => regex::escape(&whole.as_str()[1..]),
+            None => "(?s:.*?)".into(),
+        });
+        rest = whole.end();
+    }
+    expression.push_str(&regex::escape(&template[rest..]));
+    expression.push('$');
+
+    match regex::Regex::new(&expression) {
+        Ok(expression) => expression.is_match(content),
+        Err(_) => template == content,
+    }
+}
@@ -0,0 +1,50 @@
+/// Seconds since the unix epoch
+pub fn now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// Last modified time of a file in seconds since the unix epoch
+pub fn modified(path: &std::path::Path) -> Option<i64> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .map(|duration| duration.as_secs() as i64)
+}
+
+/// Converts a unix timestamp to a UTC `(year, month, day)` date
+pub fn date(timestamp: i64) -> (i64, u32, u32) {
+    let days = timestamp.div_euclid(86400);
+    let era = (days + 719468).div_euclid(146097);
+    let day_of_era = (days + 719468).rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+
+    (year_of_era + era * 400 + (month <= 2) as i64, month, day)
+}
+
+/// Formats a unix timestamp as a UTC `YYYY-MM-DD HH:MM` string
+pub fn datetime(timestamp: i64) -> String {
+    let (year, month, day) = date(timestamp);
+    let seconds = timestamp.rem_euclid(86400);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}